@@ -0,0 +1,48 @@
+use chrono::{Datelike, NaiveDate, Weekday};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use euribor_cost_chart::average::reference_average_rates;
+use euribor_cost_chart::{calculate_average_rates, AllEuriborRates, AverageOptions, EuriborRate, Series, Tenor};
+
+// Weekday fixings of the standard tenors from 1999 to 2024
+fn history() -> AllEuriborRates {
+    let start = NaiveDate::from_ymd_opt(1999, 1, 4).unwrap();
+    let end = NaiveDate::from_ymd_opt(2024, 12, 31).unwrap();
+    let mut all_rates = AllEuriborRates::new();
+
+    for (i, tenor) in Tenor::STANDARD.into_iter().enumerate() {
+        let rates = start.iter_days()
+            .take_while(|date| *date <= end)
+            .filter(|date| !matches!(date.weekday(), Weekday::Sat | Weekday::Sun))
+            .map(|date| {
+                let t = (date - start).num_days() as f64;
+                EuriborRate { date, rate: 2.0 + 0.1 * i as f64 + 2.0 * (t / 700.0).sin(), flag: None }
+            })
+            .collect();
+        all_rates.insert(tenor, Series { rates, ..Series::default() });
+    }
+    all_rates
+}
+
+fn averaging(c: &mut Criterion) {
+    let all_rates = history();
+    let options = AverageOptions::default();
+    let mut group = c.benchmark_group("average_rates_1999_2024");
+    group.sample_size(10);
+
+    for window in [360, 1800] {
+        group.bench_with_input(BenchmarkId::new("fast", window), &window, |b, &window| {
+            b.iter(|| calculate_average_rates(&all_rates, &[window], &options).unwrap())
+        });
+        group.bench_with_input(BenchmarkId::new("reference", window), &window, |b, &window| {
+            b.iter(|| reference_average_rates(&all_rates, window, &options).unwrap())
+        });
+    }
+    let windows = [360, 1080, 1800];
+    group.bench_function("fast/360,1080,1800", |b| {
+        b.iter(|| calculate_average_rates(&all_rates, &windows, &options).unwrap())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, averaging);
+criterion_main!(benches);
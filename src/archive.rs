@@ -0,0 +1,83 @@
+//! Transparent access to plain, gzipped and zipped CSV inputs.
+
+use crate::error::{Error, Result};
+use flate2::read::GzDecoder;
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::Path;
+use zip::ZipArchive;
+
+/// Contents of one CSV file, read from disk or extracted from an archive.
+#[derive(Debug, Clone)]
+pub struct InputFile {
+    /// File name, or the entry name inside a ZIP archive.
+    pub name: String,
+    pub data: Vec<u8>,
+}
+
+// Whether a path or entry name ends in the given extension, ignoring case
+fn has_extension(name: &str, extension: &str) -> bool {
+    name.to_lowercase().ends_with(extension)
+}
+
+/// Whether `path` is a ZIP archive, judged by its extension.
+pub fn is_zip(path: &Path) -> bool {
+    has_extension(&path.to_string_lossy(), ".zip")
+}
+
+// Decompress gzipped data
+fn gunzip(data: &[u8]) -> Result<Vec<u8>> {
+    let mut decompressed = Vec::new();
+    GzDecoder::new(data).read_to_end(&mut decompressed)?;
+    Ok(decompressed)
+}
+
+/// Names of the file entries of a ZIP archive.
+pub fn zip_entries(path: &Path) -> Result<Vec<String>> {
+    let archive = ZipArchive::new(File::open(path)?)?;
+    Ok(archive.file_names()
+        .filter(|name| !name.ends_with('/'))
+        .map(str::to_string)
+        .collect())
+}
+
+/// Read one entry of a ZIP archive, decompressing it if it is itself gzipped.
+pub fn read_zip_entry(path: &Path, name: &str) -> Result<InputFile> {
+    let mut archive = ZipArchive::new(File::open(path)?)?;
+    let mut entry = archive.by_name(name)?;
+    let mut data = Vec::new();
+    entry.read_to_end(&mut data)?;
+    if has_extension(name, ".gz") {
+        data = gunzip(&data)?;
+    }
+    Ok(InputFile { name: name.to_string(), data })
+}
+
+/// Read every CSV payload behind `path`.
+///
+/// A `.zip` archive yields its `.csv` and `.csv.gz` entries, a `.gz` file its
+/// decompressed contents, and any other file its raw contents.
+pub fn read_inputs(path: &Path) -> Result<Vec<InputFile>> {
+    let name = path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+
+    if is_zip(path) {
+        zip_entries(path)?.iter()
+            .filter(|entry| has_extension(entry, ".csv") || has_extension(entry, ".csv.gz"))
+            .map(|entry| read_zip_entry(path, entry))
+            .collect()
+    } else if has_extension(&name, ".gz") {
+        Ok(vec![InputFile { name, data: gunzip(&fs::read(path)?)? }])
+    } else {
+        Ok(vec![InputFile { name, data: fs::read(path)? }])
+    }
+}
+
+/// Read the single CSV payload behind `path`, failing if an archive holds several.
+pub fn read_single_input(path: &Path) -> Result<InputFile> {
+    let mut inputs = read_inputs(path)?;
+    match inputs.len() {
+        1 => Ok(inputs.remove(0)),
+        0 => Err(Error::NoData("no CSV file in the archive".to_string())),
+        n => Err(Error::Format(format!("the archive holds {} CSV files; expected one", n))),
+    }
+}
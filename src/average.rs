@@ -0,0 +1,719 @@
+//! Realized-cost averaging engine.
+
+use crate::calendar::{add_business_days, BusinessDayConvention};
+use crate::day_count::DayCount;
+use crate::error::{Error, Result};
+use crate::projection::{self, Projection};
+use crate::rates::{AllEuriborRates, EuriborRate};
+use crate::scenario::Scenario;
+use crate::tenor::{Tenor, TenorUnit};
+use chrono::{Datelike, Duration, NaiveDate};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::str::FromStr;
+
+/// How the fixing applying to an interest period is found for its reset date.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FixingLookup {
+    /// The last fixing on or before the reset date.
+    #[default]
+    Previous,
+    /// The fixing of the business day the reset date is adjusted to.
+    Adjusted(BusinessDayConvention),
+    /// The fixing this many TARGET business days before the reset date, as
+    /// in loan contracts fixing at T-2.
+    Lag(i64),
+}
+
+impl FixingLookup {
+    /// Fixing date looked up for a reset date.
+    pub fn fixing_date(&self, reset_date: NaiveDate) -> NaiveDate {
+        match self {
+            FixingLookup::Previous => reset_date,
+            FixingLookup::Adjusted(convention) => convention.adjust(reset_date),
+            FixingLookup::Lag(days) => add_business_days(reset_date, -days),
+        }
+    }
+}
+
+impl fmt::Display for FixingLookup {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FixingLookup::Previous => f.write_str("previous"),
+            FixingLookup::Adjusted(convention) => write!(f, "{}", convention),
+            FixingLookup::Lag(days) => write!(f, "t-{}", days),
+        }
+    }
+}
+
+impl FromStr for FixingLookup {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim().to_lowercase();
+        if s == "previous" {
+            return Ok(FixingLookup::Previous);
+        }
+        if let Some(days) = s.strip_prefix("t-") {
+            return days.parse()
+                .map(FixingLookup::Lag)
+                .map_err(|_| Error::InvalidOption(format!("invalid fixing lag '{}'", s)));
+        }
+        s.parse()
+            .map(FixingLookup::Adjusted)
+            .map_err(|_| Error::InvalidOption(format!("unknown fixing lookup '{}'", s)))
+    }
+}
+
+/// How the fixings of consecutive interest periods are combined into one rate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AveragingMethod {
+    /// Mean of the fixings weighted by the interest accrued in each period.
+    #[default]
+    Simple,
+    /// Annualised rate of rolling the loan over with interest compounded at every reset.
+    Compounded,
+}
+
+impl AveragingMethod {
+    /// Short description used in chart legends.
+    pub fn label(&self) -> &'static str {
+        match self {
+            AveragingMethod::Simple => "rlz avg",
+            AveragingMethod::Compounded => "rlz compounded",
+        }
+    }
+}
+
+impl fmt::Display for AveragingMethod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            AveragingMethod::Simple => "simple",
+            AveragingMethod::Compounded => "compounded",
+        })
+    }
+}
+
+impl FromStr for AveragingMethod {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "simple" | "mean" => Ok(AveragingMethod::Simple),
+            "compounded" | "compound" => Ok(AveragingMethod::Compounded),
+            _ => Err(Error::InvalidOption(format!("unknown averaging method '{}'", s))),
+        }
+    }
+}
+
+// Interest accrued over consecutive periods, combined into an annual rate
+#[derive(Clone, Copy)]
+pub(crate) struct Accrual {
+    method: AveragingMethod,
+    per_actual_year: bool,
+    interest: f64,
+    log_growth: f64,
+    year_fractions: f64,
+    days: i64,
+}
+
+impl Accrual {
+    pub(crate) fn new(options: &AverageOptions) -> Self {
+        Accrual {
+            method: options.method,
+            per_actual_year: options.per_actual_year,
+            interest: 0.0,
+            log_growth: 0.0,
+            year_fractions: 0.0,
+            days: 0,
+        }
+    }
+
+    // Add a period of `days` actual days accruing `rate` percent over `year_fraction`
+    pub(crate) fn add(&mut self, rate: f64, year_fraction: f64, days: i64) {
+        self.interest += rate * year_fraction;
+        self.log_growth += (rate / 100.0 * year_fraction).ln_1p();
+        self.year_fractions += year_fraction;
+        self.days += days;
+    }
+
+    // Periods added to this accrual after it was at `earlier`
+    fn since(&self, earlier: &Accrual) -> Accrual {
+        Accrual {
+            interest: self.interest - earlier.interest,
+            log_growth: self.log_growth - earlier.log_growth,
+            year_fractions: self.year_fractions - earlier.year_fractions,
+            days: self.days - earlier.days,
+            ..*self
+        }
+    }
+
+    // Annual rate in percent, if any period was added: per year of the day
+    // count, so a constant fixing averages to itself, or per actual year of 365 days
+    pub(crate) fn rate(&self) -> Option<f64> {
+        let years = if self.per_actual_year { self.days as f64 / 365.0 } else { self.year_fractions };
+        if self.days <= 0 || years <= 0.0 {
+            return None;
+        }
+        Some(match self.method {
+            AveragingMethod::Simple => self.interest / years,
+            AveragingMethod::Compounded => (self.log_growth / years).exp_m1() * 100.0,
+        })
+    }
+}
+
+// Business days after the last fixing of a tenor during which it still applies
+pub(crate) const MAX_STALE_BUSINESS_DAYS: i64 = 5;
+
+// Fixings of one tenor by date
+pub(crate) struct Fixings(BTreeMap<NaiveDate, f64>);
+
+impl Fixings {
+    pub(crate) fn new(rates: &[EuriborRate]) -> Self {
+        Fixings(rates.iter().map(|r| (r.date, r.rate)).collect())
+    }
+
+    // Date and value of the fixing applying to a reset date; none once the
+    // series has ended, so a discontinued tenor is not carried forward
+    pub(crate) fn lookup(&self, lookup: FixingLookup, reset_date: NaiveDate) -> Option<(NaiveDate, f64)> {
+        let fixing_date = lookup.fixing_date(reset_date);
+        let (&date, &rate) = self.0.range(..=fixing_date).next_back()?;
+        let last = self.0.keys().next_back().copied()?;
+        if date == last && fixing_date > add_business_days(last, MAX_STALE_BUSINESS_DAYS) {
+            return None;
+        }
+        Some((date, rate))
+    }
+}
+
+// First loaded date of every tenor
+fn first_dates(all_rates: &AllEuriborRates) -> BTreeMap<Tenor, NaiveDate> {
+    all_rates.iter().filter_map(|(tenor, rates)| Some((tenor, rates.first()?.date))).collect()
+}
+
+// Last loaded date of every tenor
+fn last_dates(all_rates: &AllEuriborRates) -> BTreeMap<Tenor, NaiveDate> {
+    all_rates.iter().filter_map(|(tenor, rates)| Some((tenor, rates.last()?.date))).collect()
+}
+
+// Number of days from `start_date` to the last fixing of a tenor
+fn days_until(start_date: NaiveDate, last_date: Option<&NaiveDate>) -> i64 {
+    last_date.map_or(0, |last| (*last - start_date).num_days() + 1)
+}
+
+// Reset dates, as day offsets from the first loaded date, of every schedule a
+// tenor can follow; the schedule starting on any day is a suffix of one chain
+struct Chains {
+    resets: Vec<Vec<i64>>,
+    // Chain and position of the schedule starting on each day
+    starts: Vec<(usize, usize)>,
+}
+
+impl Chains {
+    fn new(tenor: Tenor, start_date: NaiveDate, days: i64) -> Self {
+        let mut resets: Vec<Vec<i64>> = Vec::new();
+        let mut ids: HashMap<(u32, i64), usize> = HashMap::new();
+        let mut starts = Vec::with_capacity(days as usize);
+
+        for day in 0..days {
+            let date = start_date + Duration::days(day);
+            // Month schedules are shared by dates with the same day of the
+            // month (month ends alike) and month modulo the tenor length
+            let key = match tenor.unit() {
+                TenorUnit::Week => (0, day % tenor.period_days()),
+                TenorUnit::Month => {
+                    let month_end = (date + Duration::days(1)).day() == 1;
+                    let month = date.year() as i64 * 12 + date.month0() as i64;
+                    (if month_end { 31 } else { date.day() }, month.rem_euclid(tenor.count() as i64))
+                }
+            };
+            let id = *ids.entry(key).or_insert_with(|| {
+                let mut chain = Vec::new();
+                for periods in 0.. {
+                    let offset = (tenor.advance(date, periods) - start_date).num_days();
+                    chain.push(offset);
+                    if offset >= days {
+                        break;
+                    }
+                }
+                resets.push(chain);
+                resets.len() - 1
+            });
+            let position = resets[id].binary_search(&day).expect("schedule starts on its chain");
+            starts.push((id, position));
+        }
+
+        Chains { resets, starts }
+    }
+}
+
+/// Which way the averaging window extends from each charted date.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Direction {
+    /// The cost of a loan taken out on the date, over the window that follows.
+    #[default]
+    Forward,
+    /// The cost of a loan over the window ending on the date.
+    Trailing,
+}
+
+impl Direction {
+    /// Short description used in chart legends and titles.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Direction::Forward => "forward",
+            Direction::Trailing => "trailing",
+        }
+    }
+}
+
+impl fmt::Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
+impl FromStr for Direction {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "forward" => Ok(Direction::Forward),
+            "trailing" | "backward" => Ok(Direction::Trailing),
+            _ => Err(Error::InvalidOption(format!("unknown averaging direction '{}'", s))),
+        }
+    }
+}
+
+/// How the days whose window is not fully covered by the data are treated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TailPolicy {
+    /// Average over the fixings available, shown as a distinct partial segment.
+    #[default]
+    Partial,
+    /// Leave out the days whose window is incomplete.
+    Truncate,
+    /// Complete forward windows with projected fixings, shown as a distinct segment.
+    Project,
+}
+
+impl fmt::Display for TailPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            TailPolicy::Partial => "partial",
+            TailPolicy::Truncate => "truncate",
+            TailPolicy::Project => "project",
+        })
+    }
+}
+
+impl FromStr for TailPolicy {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "partial" => Ok(TailPolicy::Partial),
+            "truncate" => Ok(TailPolicy::Truncate),
+            "project" | "projected" => Ok(TailPolicy::Project),
+            _ => Err(Error::InvalidOption(format!("unknown tail policy '{}'", s))),
+        }
+    }
+}
+
+// First day, end (exclusive) and accrual cut-off of the window charted on
+// `day`, as day offsets, for data covering `days` days
+fn window_bounds(direction: Direction, day: i64, window: i64, days: i64) -> (i64, i64, i64) {
+    match direction {
+        Direction::Forward => (day, day + std::cmp::min(window, days - day), days),
+        Direction::Trailing => (std::cmp::max(0, day + 1 - window), day + 1, day + 1),
+    }
+}
+
+/// Options of the averaging engine.
+#[derive(Debug, Clone, Default)]
+pub struct AverageOptions {
+    pub fixing_lookup: FixingLookup,
+    /// Convention the interest of each period accrues under.
+    pub day_count: DayCount,
+    pub method: AveragingMethod,
+    /// Divide the interest by actual years of 365 days, giving the effective
+    /// annual cost, instead of by the year fractions it accrued over.
+    pub per_actual_year: bool,
+    pub direction: Direction,
+    pub tail: TailPolicy,
+    /// Source of the fixings completing the windows with [`TailPolicy::Project`].
+    pub projection: Projection,
+}
+
+/// Realized average rates of every loaded tenor over one window length.
+#[derive(Debug, Clone)]
+pub struct AverageRates {
+    /// First loaded date of any tenor.
+    pub start_date: NaiveDate,
+    /// Last day whose forward window, or first day whose trailing window, is
+    /// fully covered by data (see [`AverageRates::time_mark`] for a single tenor).
+    pub averaged_time_mark: NaiveDate,
+    /// Length of the averaging window in days.
+    pub averaged_time_days: i64,
+    pub method: AveragingMethod,
+    /// Whether the averages are per actual year of 365 days.
+    pub per_actual_year: bool,
+    pub direction: Direction,
+    /// Treatment of the days beyond `averaged_time_mark`.
+    pub tail: TailPolicy,
+    pub projection: Projection,
+    /// Name of the rate scenario that completes the windows, if any.
+    pub scenario: Option<String>,
+    /// First loaded date of each tenor.
+    pub start_dates: BTreeMap<Tenor, NaiveDate>,
+    /// Last loaded date of each tenor.
+    pub end_dates: BTreeMap<Tenor, NaiveDate>,
+    /// One average per calendar day from the tenor's first to its last
+    /// loaded date (or from or to its time mark when the tail is truncated).
+    pub averages: BTreeMap<Tenor, Vec<f64>>,
+}
+
+impl AverageRates {
+    /// Last day whose forward window, or first day whose trailing window, is
+    /// fully covered by the data of `tenor`.
+    ///
+    /// Forward windows of a tenor that ended before the other tenors are
+    /// complete only up to its own last date, and trailing windows of a tenor
+    /// that started after them only from its own first date.
+    pub fn time_mark(&self, tenor: Tenor) -> NaiveDate {
+        match self.direction {
+            Direction::Forward => match self.end_dates.get(&tenor) {
+                Some(&end) => std::cmp::min(self.averaged_time_mark, end - Duration::days(self.averaged_time_days)),
+                None => self.averaged_time_mark,
+            },
+            Direction::Trailing => {
+                std::cmp::max(self.averaged_time_mark, self.first_date(tenor) + Duration::days(self.averaged_time_days - 1))
+            }
+        }
+    }
+
+    /// Date of the first entry of the averages of `tenor`.
+    pub fn first_date(&self, tenor: Tenor) -> NaiveDate {
+        self.start_dates.get(&tenor).copied().unwrap_or(self.start_date)
+    }
+
+    // Keep only the days whose window is fully covered by data
+    fn truncate(&mut self) {
+        let marks: BTreeMap<Tenor, NaiveDate> = self.averages.keys().map(|&t| (t, self.time_mark(t))).collect();
+        let starts: BTreeMap<Tenor, NaiveDate> = self.averages.keys().map(|&t| (t, self.first_date(t))).collect();
+        for (tenor, averages) in &mut self.averages {
+            let days = (marks[tenor] - starts[tenor]).num_days();
+            match self.direction {
+                Direction::Forward => averages.truncate((days + 1).max(0) as usize),
+                Direction::Trailing => {
+                    averages.drain(..(days.max(0) as usize).min(averages.len()));
+                    self.start_dates.insert(*tenor, marks[tenor]);
+                }
+            }
+        }
+        if self.direction == Direction::Trailing {
+            self.start_date = self.averaged_time_mark;
+        }
+        self.tail = TailPolicy::Truncate;
+    }
+
+    /// Dates from `start_date` to the last entry of any averaged series.
+    pub fn dates(&self) -> impl Iterator<Item = NaiveDate> + '_ {
+        let len = self.averages.iter()
+            .map(|(&tenor, averages)| (self.first_date(tenor) - self.start_date).num_days() + averages.len() as i64)
+            .max()
+            .unwrap_or(0);
+        (0..len).map(move |j| self.start_date + Duration::days(j))
+    }
+
+    /// Dates matching the entries of the averaged series of `tenor`.
+    pub fn tenor_dates(&self, tenor: Tenor) -> impl Iterator<Item = NaiveDate> + '_ {
+        let start = self.first_date(tenor);
+        let len = self.averages.get(&tenor).map_or(0, Vec::len);
+        (0..len).map(move |j| start + Duration::days(j as i64))
+    }
+}
+
+/// Calculate the realized average rate of every loaded tenor for each day, for
+/// each of the window lengths in `windows` (in days).
+///
+/// Starting on each day (or, in [`Direction::Trailing`] mode, on the first
+/// day of the window ending on it), the tenor is rolled over at calendar-month (or
+/// week) intervals until the window is covered. Each interest period accrues
+/// its fixing under the configured day count, and the average is the total
+/// interest divided by the year fractions accrued, so a constant fixing
+/// averages to itself. With `per_actual_year` it is instead the interest per
+/// actual year (365 days) elapsed, i.e. the effective annual cost a borrower
+/// paid. With [`AveragingMethod::Compounded`] the interest is reinvested at
+/// every reset and the result is the annualised rollover rate.
+///
+/// Each reset date is resolved to a fixing with the configured lookup. When
+/// no fixing was published on the date looked up (a weekend, a holiday or a
+/// dropped value), the last fixing before it applies, so every interest
+/// period within the data is accounted for. The averages of each tenor start
+/// at its own first fixing and end at its own last one, so a tenor is neither
+/// charted as a zero rate before it was published nor carried forward after
+/// it was discontinued.
+/// Forward windows shrink near the end of a tenor's data and trailing
+/// windows near its start; the last period of a trailing window is cut off
+/// at the charted day.
+///
+/// The days whose window is not fully covered by the data are handled by the
+/// tail policy; [`TailPolicy::Project`] extends every series with projected
+/// fixings (see [`projection::project`]) for forward windows.
+///
+/// Reset schedules are precomputed once per tenor on day-indexed vectors
+/// with running interest totals and shared by all windows, so each day costs
+/// a binary search instead of a walk over its window. The result matches
+/// [`reference_average_rates`] up to rounding.
+pub fn calculate_average_rates(all_rates: &AllEuriborRates, windows: &[i64], options: &AverageOptions) -> Result<Vec<AverageRates>> {
+    match options.tail {
+        TailPolicy::Partial => average_rates_over(all_rates, windows, options),
+        TailPolicy::Truncate => {
+            let mut results = average_rates_over(all_rates, windows, options)?;
+            results.iter_mut().for_each(AverageRates::truncate);
+            Ok(results)
+        }
+        TailPolicy::Project => projected_average_rates(all_rates, windows, options, |until| {
+            projection::project(all_rates, options.projection, until)
+        }),
+    }
+}
+
+/// Calculate forward average rates with the windows completed by a rate scenario.
+///
+/// Like [`calculate_average_rates`] with [`TailPolicy::Project`], but the
+/// fixings after the end of the data follow `scenario`; the results carry
+/// its name.
+pub fn scenario_average_rates(all_rates: &AllEuriborRates, scenario: &Scenario, windows: &[i64], options: &AverageOptions) -> Result<Vec<AverageRates>> {
+    let mut results = projected_average_rates(all_rates, windows, options, |until| Ok(scenario.apply(all_rates, until)))?;
+    for result in &mut results {
+        result.scenario = Some(scenario.name.clone());
+    }
+    Ok(results)
+}
+
+// Averages of the loaded days with the forward windows completed by the
+// rates that `project` returns, extended with future fixings up to a date
+pub(crate) fn projected_average_rates<F>(all_rates: &AllEuriborRates, windows: &[i64], options: &AverageOptions, project: F) -> Result<Vec<AverageRates>>
+where
+    F: FnOnce(NaiveDate) -> Result<AllEuriborRates>,
+{
+    if options.direction != Direction::Forward {
+        return Err(Error::InvalidOption("only forward windows can be completed with projected rates".to_string()));
+    }
+    let end_date = all_rates.end_date().ok_or_else(|| Error::NoData("no rates loaded".to_string()))?;
+    // Leave room for the last interest period of the longest window
+    let longest = windows.iter().copied().max().unwrap_or(0);
+    let projected = project(end_date + Duration::days(longest.max(0) + 366))?;
+    let mut results = average_rates_over(&projected, windows, options)?;
+
+    let end_dates = last_dates(all_rates);
+    for result in &mut results {
+        result.averaged_time_mark = end_date - Duration::days(result.averaged_time_days);
+        result.tail = TailPolicy::Project;
+        for (tenor, averages) in &mut result.averages {
+            averages.truncate(days_until(result.start_dates[tenor], end_dates.get(tenor)) as usize);
+        }
+        result.end_dates = end_dates.clone();
+    }
+    Ok(results)
+}
+
+// Averages of every window over the given rates, tail included
+fn average_rates_over(all_rates: &AllEuriborRates, windows: &[i64], options: &AverageOptions) -> Result<Vec<AverageRates>> {
+    let (start_date, end_date) = match (all_rates.start_date(), all_rates.end_date()) {
+        (Some(start), Some(end)) => (start, end),
+        _ => return Err(Error::NoData("no rates loaded".to_string())),
+    };
+
+    let start_dates = first_dates(all_rates);
+    let end_dates = last_dates(all_rates);
+    let mut results: Vec<AverageRates> = windows.iter()
+        .map(|&averaged_time_days| AverageRates {
+            start_date,
+            averaged_time_mark: match options.direction {
+                Direction::Forward => end_date - Duration::days(averaged_time_days),
+                Direction::Trailing => start_date + Duration::days(averaged_time_days - 1),
+            },
+            averaged_time_days,
+            method: options.method,
+            per_actual_year: options.per_actual_year,
+            direction: options.direction,
+            tail: options.tail,
+            projection: options.projection,
+            scenario: None,
+            start_dates: start_dates.clone(),
+            end_dates: end_dates.clone(),
+            averages: BTreeMap::new(),
+        })
+        .collect();
+
+    // Every tenor is averaged from its own first fixing, so the days before
+    // a tenor was published get no average instead of a zero rate
+    for (tenor, rates) in all_rates.iter() {
+        let tenor_start = start_dates.get(&tenor).copied().unwrap_or(start_date);
+        let date = |offset: i64| tenor_start + Duration::days(offset);
+        let days = days_until(tenor_start, end_dates.get(&tenor));
+        let fixings = Fixings::new(rates);
+        let fixing_by_day: Vec<Option<f64>> = (0..days)
+            .map(|day| fixings.lookup(options.fixing_lookup, date(day)).map(|(_, rate)| rate))
+            .collect();
+        let chains = Chains::new(tenor, tenor_start, days);
+
+        // Accrual of the full periods before each position of every chain
+        let totals: Vec<Vec<Accrual>> = chains.resets.iter()
+            .map(|chain| {
+                let mut accrual = Accrual::new(options);
+                let mut totals = vec![accrual];
+                for pair in chain.windows(2) {
+                    if let Some(rate) = fixing_by_day[pair[0] as usize] {
+                        accrual.add(rate, options.day_count.year_fraction(date(pair[0]), date(pair[1])), pair[1] - pair[0]);
+                    }
+                    totals.push(accrual);
+                }
+                totals
+            })
+            .collect();
+
+        for result in &mut results {
+            let mut tenor_averages = Vec::with_capacity(days as usize);
+            for day in 0..days {
+                let (first_day, window_end, cut_off) =
+                    window_bounds(options.direction, day, result.averaged_time_days, days);
+                if window_end <= first_day {
+                    tenor_averages.push(0.0);
+                    continue;
+                }
+                let (id, first) = chains.starts[first_day as usize];
+                let chain = &chains.resets[id];
+                let last = chain.partition_point(|&reset| reset < window_end) - 1;
+                let mut accrual = totals[id][last].since(&totals[id][first]);
+
+                // The last period is cut off at the end of the data or the trailing window
+                if let Some(rate) = fixing_by_day[chain[last] as usize] {
+                    let period_end = std::cmp::min(chain[last + 1], cut_off);
+                    let year_fraction = options.day_count.year_fraction(date(chain[last]), date(period_end));
+                    accrual.add(rate, year_fraction, period_end - chain[last]);
+                }
+                tenor_averages.push(accrual.rate().unwrap_or(0.0));
+            }
+            result.averages.insert(tenor, tenor_averages);
+        }
+    }
+
+    Ok(results)
+}
+
+/// Straightforward implementation of [`calculate_average_rates`] for a single
+/// window that walks the window of every day; kept as a reference for tests
+/// and benchmarks.
+pub fn reference_average_rates(all_rates: &AllEuriborRates, averaged_time_days: i64, options: &AverageOptions) -> Result<AverageRates> {
+    let (start_date, end_date) = match (all_rates.start_date(), all_rates.end_date()) {
+        (Some(start), Some(end)) => (start, end),
+        _ => return Err(Error::NoData("no rates loaded".to_string())),
+    };
+
+    let averaged_time_mark = match options.direction {
+        Direction::Forward => end_date - Duration::days(averaged_time_days),
+        Direction::Trailing => start_date + Duration::days(averaged_time_days - 1),
+    };
+    let start_dates = first_dates(all_rates);
+    let end_dates = last_dates(all_rates);
+    let mut averages = BTreeMap::new();
+
+    for (tenor, rates) in all_rates.iter() {
+        let tenor_start = start_dates.get(&tenor).copied().unwrap_or(start_date);
+        let days = days_until(tenor_start, end_dates.get(&tenor));
+        let fixings = Fixings::new(rates);
+        let mut tenor_averages = Vec::new();
+        let mut current_date = tenor_start;
+
+        while current_date < tenor_start + Duration::days(days) {
+            let mut accrual = Accrual::new(options);
+            let day = (current_date - tenor_start).num_days();
+            let (first_day, window_end, cut_off) = window_bounds(options.direction, day, averaged_time_days, days);
+            let loan_start = tenor_start + Duration::days(first_day);
+            let window_end = tenor_start + Duration::days(window_end);
+            let cut_off = tenor_start + Duration::days(cut_off);
+            let mut check_date = loan_start;
+            let mut resets = 0;
+
+            while check_date < window_end {
+                resets += 1;
+                let next_reset = tenor.advance(loan_start, resets);
+                if let Some((_, rate)) = fixings.lookup(options.fixing_lookup, check_date) {
+                    let period_end = std::cmp::min(next_reset, cut_off);
+                    let year_fraction = options.day_count.year_fraction(check_date, period_end);
+                    accrual.add(rate, year_fraction, (period_end - check_date).num_days());
+                }
+                check_date = next_reset;
+            }
+
+            tenor_averages.push(accrual.rate().unwrap_or(0.0));
+            current_date += Duration::days(1);
+        }
+
+        averages.insert(tenor, tenor_averages);
+    }
+
+    Ok(AverageRates {
+        start_date,
+        averaged_time_mark,
+        averaged_time_days,
+        method: options.method,
+        per_actual_year: options.per_actual_year,
+        direction: options.direction,
+        tail: TailPolicy::Partial,
+        projection: options.projection,
+        scenario: None,
+        start_dates,
+        end_dates,
+        averages,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn fixing_dates_on_a_weekend_and_over_easter() {
+        use BusinessDayConvention::*;
+        let lookups = [
+            FixingLookup::Previous,
+            FixingLookup::Adjusted(Following),
+            FixingLookup::Adjusted(ModifiedFollowing),
+            FixingLookup::Adjusted(Preceding),
+            FixingLookup::Lag(2),
+        ];
+        // Saturday, Good Friday, Easter Monday and the Saturday before it at the end of March
+        let expected = [
+            (date(2024, 3, 2), [date(2024, 3, 2), date(2024, 3, 4), date(2024, 3, 4), date(2024, 3, 1), date(2024, 2, 28)]),
+            (date(2024, 3, 29), [date(2024, 3, 29), date(2024, 4, 2), date(2024, 3, 28), date(2024, 3, 28), date(2024, 3, 26)]),
+            (date(2024, 4, 1), [date(2024, 4, 1), date(2024, 4, 2), date(2024, 4, 2), date(2024, 3, 28), date(2024, 3, 26)]),
+            (date(2024, 3, 30), [date(2024, 3, 30), date(2024, 4, 2), date(2024, 3, 28), date(2024, 3, 28), date(2024, 3, 26)]),
+        ];
+        for (reset_date, fixing_dates) in expected {
+            for (lookup, fixing_date) in lookups.iter().zip(fixing_dates) {
+                assert_eq!(lookup.fixing_date(reset_date), fixing_date, "{} on {}", lookup, reset_date);
+            }
+        }
+    }
+
+    #[test]
+    fn lookup_falls_back_to_the_last_fixing_before_the_date() {
+        let rates: Vec<EuriborRate> = [(date(2024, 3, 27), 3.9), (date(2024, 3, 28), 3.8), (date(2024, 4, 2), 3.7)]
+            .into_iter()
+            .map(|(date, rate)| EuriborRate { date, rate, flag: None })
+            .collect();
+        let fixings = Fixings::new(&rates);
+        assert_eq!(fixings.lookup(FixingLookup::Previous, date(2024, 4, 1)), Some((date(2024, 3, 28), 3.8)));
+        assert_eq!(fixings.lookup(FixingLookup::Lag(2), date(2024, 4, 2)), Some((date(2024, 3, 27), 3.9)));
+        assert_eq!(fixings.lookup(FixingLookup::Previous, date(2024, 3, 26)), None);
+    }
+}
@@ -0,0 +1,229 @@
+//! TARGET2 business-day calendar.
+//!
+//! Euribor is fixed only on days when TARGET2 is open. This module knows the
+//! closing days and the usual conventions for moving a date that falls on one.
+
+use crate::error::{Error, Result};
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use std::fmt;
+use std::str::FromStr;
+
+// Easter Sunday of a year (anonymous Gregorian algorithm)
+fn easter_sunday(year: i32) -> NaiveDate {
+    let a = year % 19;
+    let b = year / 100;
+    let c = year % 100;
+    let d = b / 4;
+    let e = b % 4;
+    let f = (b + 8) / 25;
+    let g = (b - f + 1) / 3;
+    let h = (19 * a + b - d - g + 15) % 30;
+    let i = c / 4;
+    let k = c % 4;
+    let l = (32 + 2 * e + 2 * i - h - k) % 7;
+    let m = (a + 11 * h + 22 * l) / 451;
+    let month = (h + l - 7 * m + 114) / 31;
+    let day = (h + l - 7 * m + 114) % 31 + 1;
+    NaiveDate::from_ymd_opt(year, month as u32, day as u32).expect("valid Easter date")
+}
+
+/// Name of the TARGET holiday falling on `date`, if any.
+///
+/// Covers the closing days in force since 2000 (New Year's Day, Good Friday,
+/// Easter Monday, Labour Day, Christmas Day and Boxing Day) and the extra
+/// New Year's Eve closings of 1999 to 2001.
+pub fn target_holiday(date: NaiveDate) -> Option<&'static str> {
+    let year = date.year();
+    let easter = easter_sunday(year);
+    match (date.month(), date.day()) {
+        (1, 1) => Some("New Year's Day"),
+        (12, 25) => Some("Christmas Day"),
+        (12, 26) if year >= 2000 => Some("Boxing Day"),
+        (12, 31) if (1999..=2001).contains(&year) => Some("New Year's Eve"),
+        (5, 1) if year >= 2000 => Some("Labour Day"),
+        _ if year >= 2000 && date == easter - Duration::days(2) => Some("Good Friday"),
+        _ if year >= 2000 && date == easter + Duration::days(1) => Some("Easter Monday"),
+        _ => None,
+    }
+}
+
+/// Whether `date` falls on a Saturday or Sunday.
+pub fn is_weekend(date: NaiveDate) -> bool {
+    matches!(date.weekday(), Weekday::Sat | Weekday::Sun)
+}
+
+/// Whether TARGET is open, i.e. a Euribor fixing is expected, on `date`.
+pub fn is_business_day(date: NaiveDate) -> bool {
+    !is_weekend(date) && target_holiday(date).is_none()
+}
+
+// Last day of a month
+fn last_day_of_month(year: i32, month: u32) -> NaiveDate {
+    let (year, month) = if month == 12 { (year + 1, 1) } else { (year, month + 1) };
+    NaiveDate::from_ymd_opt(year, month, 1).expect("valid month") - Duration::days(1)
+}
+
+/// Add `months` calendar months to `date` with the end-of-month rule.
+///
+/// A date on the last day of its month moves to the last day of the target
+/// month; other dates keep their day, clamped to the length of the month.
+pub fn add_months(date: NaiveDate, months: u32) -> NaiveDate {
+    let total = date.year() * 12 + date.month0() as i32 + months as i32;
+    let (year, month) = (total.div_euclid(12), total.rem_euclid(12) as u32 + 1);
+    let last = last_day_of_month(year, month);
+    if date == last_day_of_month(date.year(), date.month()) || date.day() > last.day() {
+        last
+    } else {
+        last.with_day(date.day()).expect("day within month")
+    }
+}
+
+/// Next business day on or after `date`.
+pub fn following(date: NaiveDate) -> NaiveDate {
+    let mut date = date;
+    while !is_business_day(date) {
+        date += Duration::days(1);
+    }
+    date
+}
+
+/// Last business day on or before `date`.
+pub fn preceding(date: NaiveDate) -> NaiveDate {
+    let mut date = date;
+    while !is_business_day(date) {
+        date -= Duration::days(1);
+    }
+    date
+}
+
+/// Move `date` by `days` business days, backwards for negative counts.
+///
+/// A non-business start date is first rolled in the direction of travel,
+/// so `add_business_days(saturday, -2)` is the Wednesday before.
+pub fn add_business_days(date: NaiveDate, days: i64) -> NaiveDate {
+    let mut date = if days < 0 { preceding(date) } else { following(date) };
+    for _ in 0..days.abs() {
+        date = if days < 0 { preceding(date - Duration::days(1)) } else { following(date + Duration::days(1)) };
+    }
+    date
+}
+
+/// TARGET holidays falling on weekdays between `start` and `end` inclusive.
+pub fn closing_days(start: NaiveDate, end: NaiveDate) -> Vec<NaiveDate> {
+    start.iter_days()
+        .take_while(|d| *d <= end)
+        .filter(|d| !is_weekend(*d) && target_holiday(*d).is_some())
+        .collect()
+}
+
+/// Rule for moving a date that falls on a TARGET closing day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BusinessDayConvention {
+    /// The next business day.
+    Following,
+    /// The next business day, unless it is in the next month; then the previous one.
+    #[default]
+    ModifiedFollowing,
+    /// The previous business day.
+    Preceding,
+}
+
+impl BusinessDayConvention {
+    /// Business day that `date` is moved to under this convention.
+    pub fn adjust(&self, date: NaiveDate) -> NaiveDate {
+        match self {
+            BusinessDayConvention::Following => following(date),
+            BusinessDayConvention::Preceding => preceding(date),
+            BusinessDayConvention::ModifiedFollowing => {
+                let next = following(date);
+                if next.month() == date.month() { next } else { preceding(date) }
+            }
+        }
+    }
+}
+
+impl fmt::Display for BusinessDayConvention {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            BusinessDayConvention::Following => "following",
+            BusinessDayConvention::ModifiedFollowing => "modified-following",
+            BusinessDayConvention::Preceding => "preceding",
+        })
+    }
+}
+
+impl FromStr for BusinessDayConvention {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "following" | "f" => Ok(BusinessDayConvention::Following),
+            "modified-following" | "modified" | "mf" => Ok(BusinessDayConvention::ModifiedFollowing),
+            "preceding" | "p" => Ok(BusinessDayConvention::Preceding),
+            _ => Err(Error::InvalidOption(format!("unknown business-day convention '{}'", s))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn easter_sunday_matches_known_dates() {
+        let known = [(2000, 4, 23), (2008, 3, 23), (2011, 4, 24), (2019, 4, 21), (2024, 3, 31), (2038, 4, 25)];
+        for (year, month, day) in known {
+            assert_eq!(easter_sunday(year), date(year, month, day));
+        }
+    }
+
+    #[test]
+    fn target_holidays() {
+        assert_eq!(target_holiday(date(2024, 3, 29)), Some("Good Friday"));
+        assert_eq!(target_holiday(date(2024, 4, 1)), Some("Easter Monday"));
+        assert_eq!(target_holiday(date(2024, 5, 1)), Some("Labour Day"));
+        assert_eq!(target_holiday(date(2024, 12, 26)), Some("Boxing Day"));
+        assert_eq!(target_holiday(date(2024, 12, 31)), None);
+
+        // New Year's Eve closed TARGET from 1999 to 2001 only
+        assert_eq!(target_holiday(date(1999, 12, 31)), Some("New Year's Eve"));
+        assert_eq!(target_holiday(date(2001, 12, 31)), Some("New Year's Eve"));
+        assert_eq!(target_holiday(date(2002, 12, 31)), None);
+        // Easter and Labour Day closings started in 2000
+        assert_eq!(target_holiday(date(1999, 4, 2)), None);
+        assert_eq!(target_holiday(date(1999, 4, 5)), None);
+
+        assert_eq!(closing_days(date(2024, 1, 1), date(2024, 12, 31)), [
+            date(2024, 1, 1), date(2024, 3, 29), date(2024, 4, 1), date(2024, 5, 1), date(2024, 12, 25), date(2024, 12, 26),
+        ]);
+    }
+
+    #[test]
+    fn add_months_keeps_month_ends() {
+        assert_eq!(add_months(date(2024, 1, 31), 1), date(2024, 2, 29));
+        assert_eq!(add_months(date(2023, 1, 31), 1), date(2023, 2, 28));
+        assert_eq!(add_months(date(2024, 1, 30), 1), date(2024, 2, 29));
+        assert_eq!(add_months(date(2024, 2, 29), 1), date(2024, 3, 31));
+        assert_eq!(add_months(date(2023, 2, 28), 12), date(2024, 2, 29));
+        assert_eq!(add_months(date(2024, 2, 29), 12), date(2025, 2, 28));
+        assert_eq!(add_months(date(2024, 11, 30), 3), date(2025, 2, 28));
+        assert_eq!(add_months(date(2024, 1, 15), 13), date(2025, 2, 15));
+    }
+
+    #[test]
+    fn business_day_moves_skip_closing_days() {
+        // Maundy Thursday to the Tuesday after Easter Monday and back
+        assert_eq!(add_business_days(date(2024, 3, 28), 1), date(2024, 4, 2));
+        assert_eq!(add_business_days(date(2024, 4, 2), -1), date(2024, 3, 28));
+        assert_eq!(add_business_days(date(2024, 3, 30), 0), date(2024, 4, 2));
+        assert_eq!(add_business_days(date(2024, 1, 6), -2), date(2024, 1, 3));
+
+        assert_eq!(BusinessDayConvention::Following.adjust(date(2024, 3, 30)), date(2024, 4, 2));
+        assert_eq!(BusinessDayConvention::ModifiedFollowing.adjust(date(2024, 3, 30)), date(2024, 3, 28));
+        assert_eq!(BusinessDayConvention::Preceding.adjust(date(2024, 4, 1)), date(2024, 3, 28));
+    }
+}
@@ -0,0 +1,455 @@
+//! Plotly chart data and HTML page builders.
+
+use crate::average::{AverageRates, Direction, TailPolicy};
+use crate::error::Result;
+use crate::rates::AllEuriborRates;
+use crate::tenor::Tenor;
+use crate::calendar::closing_days;
+use chrono::{NaiveDate, NaiveDateTime};
+use serde_json::json;
+use std::ops::Range;
+
+/// Trace colors, assigned to the loaded tenors in ascending order.
+pub const COLORS: [&str; 10] = [
+    "#1f77b4", "#ff7f0e", "#2ca02c", "#d62728", "#9467bd",
+    "#8c564b", "#e377c2", "#7f7f7f", "#bcbd22", "#17becf",
+];
+
+/// Options controlling which traces are drawn.
+#[derive(Debug, Clone, Default)]
+pub struct ChartOptions {
+    /// Mark flagged observations (e.g. provisional values) on the daily-value traces.
+    pub highlight_flags: bool,
+    /// Draw the traces on an x axis without weekends and TARGET holidays.
+    pub business_days_axis: bool,
+}
+
+// Legend entry of an averaged trace, e.g. "3m (360d rlz avg)", "3m (360d trailing rlz avg)"
+// or "3m (360d rlz avg per 365d)"
+fn trace_name(tenor: Tenor, averages: &AverageRates) -> String {
+    let basis = if averages.per_actual_year { " per 365d" } else { "" };
+    match averages.direction {
+        Direction::Forward => format!("{} ({}d {}{})", tenor.label(), averages.averaged_time_days, averages.method.label(), basis),
+        Direction::Trailing => {
+            format!("{} ({}d trailing {}{})", tenor.label(), averages.averaged_time_days, averages.method.label(), basis)
+        }
+    }
+}
+
+// Entries of an averaged series starting on `start` whose window is fully covered
+// by data up to `mark`, and the incomplete tail (overlapping by one entry so the segments join)
+fn split_tail(averages: &AverageRates, start: NaiveDate, mark: NaiveDate, len: usize) -> (Range<usize>, Option<Range<usize>>) {
+    let mark = (mark - start).num_days();
+    match averages.direction {
+        Direction::Forward => {
+            let end = (mark + 1).clamp(0, len as i64) as usize;
+            (0..end, (end < len).then(|| end.saturating_sub(1)..len))
+        }
+        Direction::Trailing => {
+            let start = mark.clamp(0, len as i64) as usize;
+            (start..len, (start > 0).then(|| 0..(start + 1).min(len)))
+        }
+    }
+}
+
+// Shaded area marking the dates whose window is incomplete or projected
+fn tail_region(averages: &AverageRates, max_rate: f64) -> Option<serde_json::Value> {
+    let dates: Vec<String> = averages.dates().map(|d| d.format("%Y-%m-%d").to_string()).collect();
+    let tail = split_tail(averages, averages.start_date, averages.averaged_time_mark, dates.len()).1?;
+    let (first, last) = (&dates[tail.start], &dates[tail.end - 1]);
+    let name = match (averages.tail, averages.direction) {
+        (TailPolicy::Project, _) => format!("Projected fixings ({})", averages.projection.label()),
+        (_, Direction::Forward) => "Partial forward window".to_string(),
+        (_, Direction::Trailing) => "Partial trailing window".to_string(),
+    };
+    Some(json!({
+        "x": [first, last, last, first, first],
+        "y": [0, 0, max_rate, max_rate, 0],
+        "type": "scatter",
+        "mode": "lines",
+        "fill": "toself",
+        "fillcolor": "rgba(128, 128, 128, 0.15)",
+        "line": { "width": 0 },
+        "hoverinfo": "skip",
+        "name": name,
+        "showlegend": true
+    }))
+}
+
+// Show a trace belonging to one averaging window only while that window is selected
+fn tag_window(trace: &mut serde_json::Value, averages: &AverageRates, selected: bool) {
+    trace["meta"] = json!({ "window": averages.averaged_time_days });
+    if !selected {
+        trace["visible"] = json!(false);
+    }
+}
+
+/// Create the Plotly trace array for the averaged and daily rates.
+///
+/// The averages of every window get their own traces; only the traces of
+/// the first window are visible initially. The part of each averaged line
+/// whose window is incomplete or projected is drawn dashed and faded over a
+/// shaded area. Averages completed by a rate scenario add only that part,
+/// as a dash-dotted line named after the scenario.
+pub fn create_chart_data(all_rates: &AllEuriborRates, averages: &[AverageRates], options: &ChartOptions) -> Result<serde_json::Value> {
+    let mut traces = Vec::new();
+    // WebGL traces ignore axis range breaks
+    let trace_type = if options.business_days_axis { "scatter" } else { "scattergl" };
+    let first_window = averages.first().map(|a| a.averaged_time_days);
+
+    for (i, (tenor, rates)) in all_rates.iter().enumerate() {
+        let color = COLORS[i % COLORS.len()];
+
+        // Average rates traces
+        for window in averages {
+            if let Some(tenor_averages) = window.averages.get(&tenor) {
+                let dates: Vec<String> = window.tenor_dates(tenor).map(|d| d.format("%Y-%m-%d").to_string()).collect();
+                let name = trace_name(tenor, window);
+                let (full, tail) = split_tail(window, window.first_date(tenor), window.time_mark(tenor), tenor_averages.len());
+                let selected = Some(window.averaged_time_days) == first_window;
+
+                if let Some(scenario) = &window.scenario {
+                    if let Some(tail) = tail {
+                        let mut scenario_trace = json!({
+                            "x": dates[tail.clone()],
+                            "y": tenor_averages[tail],
+                            "type": trace_type,
+                            "mode": "lines",
+                            "name": format!("{} {}", name, scenario),
+                            "line": {
+                                "color": color,
+                                "width": 2,
+                                "dash": "dashdot"
+                            }
+                        });
+                        tag_window(&mut scenario_trace, window, selected);
+                        traces.push(scenario_trace);
+                    }
+                    continue;
+                }
+
+                let mut avg_trace = json!({
+                    "x": dates[full.clone()],
+                    "y": tenor_averages[full],
+                    "type": trace_type,
+                    "mode": "lines",
+                    "name": name,
+                    "legendgroup": name,
+                    "line": {
+                        "color": color,
+                        "width": 2
+                    }
+                });
+                tag_window(&mut avg_trace, window, selected);
+                traces.push(avg_trace);
+
+                // A tenor that ended before the others is not projected
+                let projected = window.tail == TailPolicy::Project && window.time_mark(tenor) == window.averaged_time_mark;
+                if let Some(tail) = tail {
+                    let mut tail_trace = json!({
+                        "x": dates[tail.clone()],
+                        "y": tenor_averages[tail],
+                        "type": trace_type,
+                        "mode": "lines",
+                        "name": format!("{} {}", name, if projected { "projected" } else { "partial" }),
+                        "legendgroup": name,
+                        "showlegend": false,
+                        "opacity": 0.5,
+                        "line": {
+                            "color": color,
+                            "width": 2,
+                            "dash": "dash"
+                        }
+                    });
+                    tag_window(&mut tail_trace, window, selected);
+                    traces.push(tail_trace);
+                }
+            }
+        }
+
+        // Daily rates trace
+        let mut daily_trace = json!({
+            "x": rates.iter().map(|r| r.date.format("%Y-%m-%d").to_string()).collect::<Vec<String>>(),
+            "y": rates.iter().map(|r| r.rate).collect::<Vec<f64>>(),
+            "type": trace_type,
+            "mode": "lines",
+            "name": format!("{} (daily value)", tenor.label()),
+            "line": {
+                "color": color,
+                "width": 1,
+                "dash": "dot"
+            }
+        });
+        if options.highlight_flags && rates.iter().any(|r| r.is_flagged()) {
+            daily_trace["mode"] = json!("lines+markers");
+            daily_trace["marker"] = json!({
+                "color": color,
+                "symbol": "x",
+                "size": rates.iter().map(|r| if r.is_flagged() { 8 } else { 0 }).collect::<Vec<u32>>()
+            });
+            daily_trace["text"] = json!(rates.iter().map(|r| r.flag.clone().unwrap_or_default()).collect::<Vec<String>>());
+        }
+        traces.push(daily_trace);
+    }
+
+    // Shade the incomplete or projected part of every window
+    let max_rate = all_rates.iter()
+        .flat_map(|(_, rates)| rates.iter().map(|r| r.rate))
+        .fold(f64::NEG_INFINITY, f64::max);
+
+    for window in averages.iter().filter(|a| a.scenario.is_none()) {
+        if let Some(mut region) = tail_region(window, max_rate) {
+            tag_window(&mut region, window, Some(window.averaged_time_days) == first_window);
+            traces.push(region);
+        }
+    }
+
+    Ok(json!(traces))
+}
+
+/// Descriptive text shown around the chart.
+#[derive(Debug, Clone)]
+pub struct PageInfo {
+    /// Lengths of the averaging windows in days, in the order of the window selector.
+    pub windows: Vec<i64>,
+    pub direction: Direction,
+    /// Most recent publisher update of the charted series.
+    pub last_update: Option<NaiveDateTime>,
+    /// Data sources named in the series metadata.
+    pub sources: Vec<String>,
+    /// TARGET holidays to hide from the x axis together with weekends; `None`
+    /// shows every calendar day.
+    pub closing_days: Option<Vec<NaiveDate>>,
+}
+
+impl PageInfo {
+    /// Collect the page information from the loaded rates and their averages.
+    ///
+    /// The windows are taken from the averages without a scenario.
+    pub fn new(all_rates: &AllEuriborRates, averages: &[AverageRates]) -> Self {
+        PageInfo {
+            windows: averages.iter().filter(|a| a.scenario.is_none()).map(|a| a.averaged_time_days).collect(),
+            direction: averages.first().map(|a| a.direction).unwrap_or_default(),
+            last_update: all_rates.last_update(),
+            sources: all_rates.sources(),
+            closing_days: None,
+        }
+    }
+
+    /// Hide weekends and the TARGET holidays of the charted period from the x axis.
+    pub fn hide_closing_days(&mut self, all_rates: &AllEuriborRates) {
+        if let (Some(start), Some(end)) = (all_rates.start_date(), all_rates.end_date()) {
+            self.closing_days = Some(closing_days(start, end));
+        }
+    }
+}
+
+// Escape text for inclusion in HTML
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+// Chart title for one averaging window
+fn chart_title(info: &PageInfo, window: i64) -> String {
+    let mut title = format!("Euribor rates' {}-day {} realized cost (average interest rate)", window, info.direction);
+    if let Some(last_update) = info.last_update {
+        title.push_str(&format!("<br><sub>Data last updated {}</sub>", last_update.format("%Y-%m-%d %H:%M")));
+    }
+    title
+}
+
+// Dropdown switching the averaged traces and the title between the windows
+fn window_selector(chart_data: &serde_json::Value, info: &PageInfo) -> String {
+    if info.windows.len() < 2 {
+        return String::new();
+    }
+    let traces = chart_data.as_array().map(Vec::as_slice).unwrap_or_default();
+    let buttons: Vec<serde_json::Value> = info.windows.iter()
+        .map(|&window| {
+            let visible: Vec<bool> = traces.iter()
+                .map(|t| t["meta"]["window"].as_i64().is_none_or(|w| w == window))
+                .collect();
+            json!({
+                "label": format!("{} days", window),
+                "method": "update",
+                "args": [{ "visible": visible }, { "title": chart_title(info, window) }]
+            })
+        })
+        .collect();
+    let menu = json!([{ "buttons": buttons, "direction": "down", "x": 0, "xanchor": "left", "y": 1.08, "yanchor": "bottom" }]);
+    format!(",\n            updatemenus: {}", menu)
+}
+
+/// Generate a self-contained HTML page rendering the chart data with Plotly.
+///
+/// With several averaging windows the page offers a selector switching
+/// between them.
+pub fn generate_html(chart_data: &serde_json::Value, info: &PageInfo) -> String {
+    let title = chart_title(info, info.windows.first().copied().unwrap_or_default());
+    let mut footer = Vec::new();
+    if let Some(last_update) = info.last_update {
+        footer.push(format!("Data last updated {}.", last_update.format("%Y-%m-%d %H:%M")));
+    }
+    if !info.sources.is_empty() {
+        footer.push(format!("Source: {}.", info.sources.join("; ")));
+    }
+
+    let rangebreaks = match &info.closing_days {
+        Some(days) => {
+            let days: Vec<String> = days.iter().map(|d| d.format("%Y-%m-%d").to_string()).collect();
+            format!(",\n                rangebreaks: {}", json!([{ "bounds": ["sat", "mon"] }, { "values": days }]))
+        }
+        None => String::new(),
+    };
+
+    format!(r#"
+<!DOCTYPE html>
+<html>
+<head>
+    <title>Euribor Rates Chart</title>
+    <script src="https://cdn.plot.ly/plotly-latest.min.js"></script>
+    <style>
+        #chart {{ width: 100%; height: 800px; }}
+        #footer {{ font-family: sans-serif; font-size: small; color: gray; }}
+    </style>
+</head>
+<body>
+    <div id="chart"></div>
+    <p id="footer">{2}</p>
+    <script>
+        var data = {0};
+        var layout = {{
+            title: {1},
+            showlegend: true,
+            xaxis: {{ 
+                title: 'Date', 
+                type: 'date',
+                rangeslider: {{visible: true}}{3}
+            }},
+            yaxis: {{ 
+                title: 'Interest rate (%)',
+                dtick: 0.5
+            }},
+            dragmode: 'zoom'{4}
+        }};
+        var config = {{
+            scrollZoom: true,
+            modeBarButtonsToAdd: ['drawline', 'drawopenpath', 'drawclosedpath', 'drawcircle', 'drawrect', 'eraseshape']
+        }};
+        
+        Plotly.newPlot('chart', data, layout, config);
+    </script>
+</body>
+</html>
+    "#, chart_data, serde_json::Value::String(title), escape_html(&footer.join(" ")), rangebreaks,
+        window_selector(chart_data, info))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::average::{calculate_average_rates, AverageOptions, AveragingMethod};
+    use crate::projection::Projection;
+    use crate::rates::{EuriborRate, Series};
+    use chrono::Duration;
+    use std::collections::BTreeMap;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    // Ten days of 3m averages starting on 1 Jan 2024 with the given time mark
+    fn averages(direction: Direction, tail: TailPolicy, mark: NaiveDate) -> AverageRates {
+        AverageRates {
+            start_date: date(2024, 1, 1),
+            averaged_time_mark: mark,
+            averaged_time_days: 30,
+            method: AveragingMethod::Simple,
+            per_actual_year: false,
+            direction,
+            tail,
+            projection: Projection::Flat,
+            scenario: None,
+            start_dates: BTreeMap::from([(Tenor::months(3), date(2024, 1, 1))]),
+            end_dates: BTreeMap::from([(Tenor::months(3), date(2024, 1, 10))]),
+            averages: BTreeMap::from([(Tenor::months(3), vec![3.0; 10])]),
+        }
+    }
+
+    #[test]
+    fn forward_tail_overlaps_the_full_segment_by_one_entry() {
+        let forward = averages(Direction::Forward, TailPolicy::Partial, date(2024, 1, 5));
+        let start = forward.start_date;
+        assert_eq!(split_tail(&forward, start, date(2024, 1, 5), 10), (0..5, Some(4..10)));
+        assert_eq!(split_tail(&forward, start, date(2024, 1, 10), 10), (0..10, None));
+        assert_eq!(split_tail(&forward, start, date(2024, 1, 20), 10), (0..10, None));
+        assert_eq!(split_tail(&forward, start, date(2023, 12, 1), 10), (0..0, Some(0..10)));
+        // A series starting later is split at the same date
+        assert_eq!(split_tail(&forward, date(2024, 1, 3), date(2024, 1, 5), 8), (0..3, Some(2..8)));
+    }
+
+    #[test]
+    fn trailing_tail_precedes_the_full_segment() {
+        let trailing = averages(Direction::Trailing, TailPolicy::Partial, date(2024, 1, 4));
+        let start = trailing.start_date;
+        assert_eq!(split_tail(&trailing, start, date(2024, 1, 4), 10), (3..10, Some(0..4)));
+        assert_eq!(split_tail(&trailing, start, date(2024, 1, 1), 10), (0..10, None));
+        assert_eq!(split_tail(&trailing, start, date(2024, 2, 1), 10), (10..10, Some(0..10)));
+    }
+
+    #[test]
+    fn tail_region_shades_the_incomplete_dates() {
+        let forward = averages(Direction::Forward, TailPolicy::Partial, date(2024, 1, 7));
+        let region = tail_region(&forward, 5.0).unwrap();
+        assert_eq!(region["x"], json!(["2024-01-07", "2024-01-10", "2024-01-10", "2024-01-07", "2024-01-07"]));
+        assert_eq!(region["y"], json!([0, 0, 5.0, 5.0, 0]));
+        assert_eq!(region["name"], "Partial forward window");
+
+        let projected = averages(Direction::Forward, TailPolicy::Project, date(2024, 1, 7));
+        assert_eq!(tail_region(&projected, 5.0).unwrap()["name"], "Projected fixings (last fixing)");
+
+        let trailing = averages(Direction::Trailing, TailPolicy::Partial, date(2024, 1, 4));
+        let region = tail_region(&trailing, 5.0).unwrap();
+        assert_eq!(region["x"][0], "2024-01-01");
+        assert_eq!(region["x"][1], "2024-01-04");
+        assert_eq!(region["name"], "Partial trailing window");
+
+        let complete = averages(Direction::Forward, TailPolicy::Partial, date(2024, 1, 10));
+        assert!(tail_region(&complete, 5.0).is_none());
+    }
+
+    #[test]
+    fn window_selector_shows_the_traces_of_each_window() {
+        let start = date(2024, 1, 1);
+        let rates = (0..200)
+            .map(|day| EuriborRate { date: start + Duration::days(day), rate: 3.0, flag: None })
+            .collect();
+        let mut all_rates = AllEuriborRates::new();
+        all_rates.insert(Tenor::months(3), Series { rates, ..Series::default() });
+
+        let averages = calculate_average_rates(&all_rates, &[30, 90], &AverageOptions::default()).unwrap();
+        let chart_data = create_chart_data(&all_rates, &averages, &ChartOptions::default()).unwrap();
+        let info = PageInfo::new(&all_rates, &averages);
+        let selector = window_selector(&chart_data, &info);
+        let menu: serde_json::Value = serde_json::from_str(selector.trim_start_matches(",\n            updatemenus: ")).unwrap();
+
+        let windows: Vec<Option<i64>> = chart_data.as_array().unwrap().iter().map(|t| t["meta"]["window"].as_i64()).collect();
+        // Full and partial averages and the shaded tail of each window, and the daily fixings
+        assert_eq!(windows.iter().filter(|w| **w == Some(30)).count(), 3);
+        assert_eq!(windows.iter().filter(|w| **w == Some(90)).count(), 3);
+        assert_eq!(windows.iter().filter(|w| w.is_none()).count(), 1);
+
+        let buttons = menu[0]["buttons"].as_array().unwrap();
+        assert_eq!(buttons.len(), 2);
+        for (button, window) in buttons.iter().zip([30, 90]) {
+            assert_eq!(button["label"], format!("{} days", window));
+            let visible: Vec<bool> = button["args"][0]["visible"].as_array().unwrap().iter().map(|v| v.as_bool().unwrap()).collect();
+            let expected: Vec<bool> = windows.iter().map(|w| w.is_none_or(|w| w == window)).collect();
+            assert_eq!(visible, expected);
+            assert_eq!(button["args"][1]["title"], chart_title(&info, window));
+        }
+
+        let single = PageInfo { windows: vec![30], ..info };
+        assert_eq!(window_selector(&chart_data, &single), "");
+    }
+}
@@ -0,0 +1,59 @@
+//! Day-count conventions for interest accrual.
+
+use crate::error::{Error, Result};
+use chrono::{Datelike, NaiveDate};
+use std::fmt;
+use std::str::FromStr;
+
+/// Convention turning an accrual period into a fraction of a year.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DayCount {
+    /// Actual days over 360, the Euribor convention.
+    #[default]
+    Act360,
+    /// Actual days over 365.
+    Act365,
+    /// 30/360 bond basis: every month counts as 30 days.
+    Thirty360,
+}
+
+impl DayCount {
+    /// Year fraction accrued from `start` (inclusive) to `end` (exclusive).
+    pub fn year_fraction(&self, start: NaiveDate, end: NaiveDate) -> f64 {
+        match self {
+            DayCount::Act360 => (end - start).num_days() as f64 / 360.0,
+            DayCount::Act365 => (end - start).num_days() as f64 / 365.0,
+            DayCount::Thirty360 => {
+                let d1 = start.day().min(30) as i64;
+                let d2 = if d1 == 30 { end.day().min(30) } else { end.day() } as i64;
+                let days = 360 * (end.year() - start.year()) as i64
+                    + 30 * (end.month() as i64 - start.month() as i64)
+                    + (d2 - d1);
+                days as f64 / 360.0
+            }
+        }
+    }
+}
+
+impl fmt::Display for DayCount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            DayCount::Act360 => "ACT/360",
+            DayCount::Act365 => "ACT/365",
+            DayCount::Thirty360 => "30/360",
+        })
+    }
+}
+
+impl FromStr for DayCount {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_lowercase().replace(['/', '-', '_'], "").as_str() {
+            "act360" => Ok(DayCount::Act360),
+            "act365" => Ok(DayCount::Act365),
+            "30360" => Ok(DayCount::Thirty360),
+            _ => Err(Error::InvalidOption(format!("unknown day-count convention '{}'", s))),
+        }
+    }
+}
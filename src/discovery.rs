@@ -0,0 +1,106 @@
+//! Discovery of Bundesbank Euribor series files in a directory.
+
+use crate::archive::{is_zip, read_zip_entry, zip_entries};
+use crate::error::{Error, Result};
+use crate::loader::{parse_csv, read_csv, LoadOptions};
+use crate::rates::AllEuriborRates;
+use crate::series_key::{Frequency, SeriesKey};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// A file or archive entry whose name was recognised as a Euribor series key.
+#[derive(Debug, Clone)]
+pub struct DiscoveredFile {
+    pub path: PathBuf,
+    /// Entry name when the series is stored inside the ZIP archive at `path`.
+    pub entry: Option<String>,
+    pub key: SeriesKey,
+}
+
+impl DiscoveredFile {
+    /// Printable location, e.g. `download.zip!BBIG1.D.D0.EUR.MMKT.EURIBOR.M03.BID._Z.csv`.
+    pub fn location(&self) -> String {
+        match &self.entry {
+            Some(entry) => format!("{}!{}", self.path.display(), entry),
+            None => self.path.display().to_string(),
+        }
+    }
+}
+
+/// Result of scanning a directory for series files.
+#[derive(Debug, Default)]
+pub struct Discovery {
+    /// Recognised Euribor series, sorted by frequency and tenor.
+    pub files: Vec<DiscoveredFile>,
+    /// Files and archive entries that are not named after a Euribor series key.
+    pub unrecognised: Vec<PathBuf>,
+}
+
+impl Discovery {
+    /// Frequencies for which at least one series was found.
+    pub fn frequencies(&self) -> Vec<Frequency> {
+        let mut frequencies: Vec<Frequency> = self.files.iter().map(|f| f.key.frequency).collect();
+        frequencies.sort();
+        frequencies.dedup();
+        frequencies
+    }
+}
+
+// Parse a file name such as "BBIG1.D.D0.EUR.MMKT.EURIBOR.M03.BID._Z.csv" (optionally
+// gzipped and inside a directory) into its series key
+fn series_key_from_name(name: &str) -> Option<SeriesKey> {
+    let name = name.rsplit('/').next()?;
+    let stem = name.strip_suffix(".csv.gz").or_else(|| name.strip_suffix(".csv"))?;
+    stem.parse::<SeriesKey>().ok().filter(SeriesKey::is_euribor)
+}
+
+/// Scan `dir` (not recursively) for Euribor series files.
+///
+/// Plain (`.csv`) and gzipped (`.csv.gz`) files are recognised by their
+/// name; ZIP archives are searched for entries named after a series key.
+pub fn discover<P: AsRef<Path>>(dir: P) -> Result<Discovery> {
+    let mut discovery = Discovery::default();
+
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if !path.is_file() {
+            continue;
+        }
+
+        if is_zip(&path) {
+            for name in zip_entries(&path).map_err(|e| Error::in_file(&path, e))? {
+                match series_key_from_name(&name) {
+                    Some(key) => discovery.files.push(DiscoveredFile { path: path.clone(), entry: Some(name), key }),
+                    None => discovery.unrecognised.push(PathBuf::from(format!("{}!{}", path.display(), name))),
+                }
+            }
+            continue;
+        }
+
+        match path.file_name().and_then(|n| n.to_str()).and_then(series_key_from_name) {
+            Some(key) => discovery.files.push(DiscoveredFile { path, entry: None, key }),
+            None => discovery.unrecognised.push(path),
+        }
+    }
+
+    discovery.files.sort_by_key(|f| (f.key.frequency, f.key.tenor));
+    discovery.unrecognised.sort();
+    Ok(discovery)
+}
+
+/// Load every discovered series, grouped by frequency.
+pub fn load_discovered(discovery: &Discovery, options: &LoadOptions) -> Result<BTreeMap<Frequency, AllEuriborRates>> {
+    let mut loaded: BTreeMap<Frequency, AllEuriborRates> = BTreeMap::new();
+
+    for file in &discovery.files {
+        let series = match &file.entry {
+            Some(entry) => read_zip_entry(&file.path, entry).and_then(|input| parse_csv(&input.data, options)),
+            None => read_csv(&file.path, options),
+        }
+        .map_err(|e| Error::InFile { path: PathBuf::from(file.location()), source: Box::new(e) })?;
+        loaded.entry(file.key.frequency).or_default().insert(file.key.tenor, series);
+    }
+
+    Ok(loaded)
+}
@@ -0,0 +1,186 @@
+//! Importer for Euribor series downloaded from the ECB Data Portal.
+//!
+//! Two layouts are accepted: the portal's "Download data" CSV, with a `DATE`
+//! and a `TIME PERIOD` column followed by one column per series whose header
+//! ends in the series key, e.g. "Euribor 3-month - Historical close, average
+//! of observations through period (FM.M.U2.EUR.RT.MM.EURIBOR3MD_.HSTA)"; and
+//! the SDMX-CSV returned by the data API, with one row per observation and
+//! `KEY`, `TIME_PERIOD`, `OBS_VALUE` and optionally `OBS_STATUS` and `TITLE`
+//! columns.
+
+use crate::archive::read_inputs;
+use crate::error::{Error, Result};
+use crate::loader::{decode_text, parse_date, LoadOptions};
+use crate::metadata::SeriesMetadata;
+use crate::missing::{resolve_missing, RawObservation, RawValue};
+use crate::rates::{AllEuriborRates, Series};
+use crate::source::RateSource;
+use crate::tenor::Tenor;
+use csv::{ReaderBuilder, StringRecord};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+const SOURCE_NAME: &str = "ECB Data Portal";
+
+/// A CSV file downloaded from the ECB Data Portal, possibly gzipped or zipped.
+#[derive(Debug, Clone)]
+pub struct EcbSource {
+    pub path: PathBuf,
+}
+
+impl EcbSource {
+    pub fn new<P: Into<PathBuf>>(path: P) -> Self {
+        EcbSource { path: path.into() }
+    }
+}
+
+impl RateSource for EcbSource {
+    fn name(&self) -> String {
+        format!("{} ({})", SOURCE_NAME, self.path.display())
+    }
+
+    fn load(&self, options: &LoadOptions) -> Result<AllEuriborRates> {
+        let mut all_rates = AllEuriborRates::new();
+        for input in read_inputs(&self.path).map_err(|e| Error::in_file(&self.path, e))? {
+            let loaded = parse_ecb_csv(&input.data, options).map_err(|e| Error::in_file(&self.path, e))?;
+            for (tenor, series) in loaded.into_series() {
+                all_rates.insert(tenor, series);
+            }
+        }
+        Ok(all_rates)
+    }
+}
+
+/// Tenor of an ECB Euribor series key such as `FM.M.U2.EUR.RT.MM.EURIBOR3MD_.HSTA`.
+pub fn tenor_from_ecb_key(key: &str) -> Option<Tenor> {
+    let code = key.split('.')
+        .find_map(|part| part.strip_prefix("EURIBOR"))?
+        .trim_end_matches('_')
+        .strip_suffix('D')?;
+    let (count, unit) = code.split_at(code.len().checked_sub(1)?);
+    let count: u32 = count.parse().ok().filter(|&count| count > 0)?;
+    match unit {
+        "W" => Some(Tenor::weeks(count)),
+        "M" => Some(Tenor::months(count)),
+        "Y" => Some(Tenor::months(12 * count)),
+        _ => None,
+    }
+}
+
+// Map an SDMX observation status to a flag; "A" (normal value) carries no flag
+fn status_flag(status: &str) -> Option<String> {
+    match status.trim() {
+        "" | "A" => None,
+        "P" => Some("Provisional value".to_string()),
+        "E" => Some("Estimated value".to_string()),
+        "M" => Some("Missing value".to_string()),
+        other => Some(other.to_string()),
+    }
+}
+
+// Read an ECB value field; the portal writes "NaN" for missing observations
+fn parse_value(field: &str) -> RawValue {
+    if field.trim().eq_ignore_ascii_case("nan") {
+        RawValue::NoValue
+    } else {
+        RawValue::parse(field, false)
+    }
+}
+
+// Split a portal column header "Title (KEY)" into its title and series key
+fn split_column_header(header: &str) -> Option<(String, String)> {
+    let header = header.trim();
+    let open = header.rfind('(')?;
+    let key = header[open + 1..].strip_suffix(')')?.trim();
+    if !key.contains('.') {
+        return None;
+    }
+    Some((header[..open].trim().to_string(), key.to_string()))
+}
+
+// Series being collected, keyed by series key
+#[derive(Default)]
+struct Pending {
+    metadata: SeriesMetadata,
+    raw: Vec<RawObservation>,
+}
+
+/// Parse an ECB Data Portal CSV file into a rate collection.
+///
+/// Series whose key does not name a Euribor tenor are ignored.
+pub fn parse_ecb_csv(data: &[u8], options: &LoadOptions) -> Result<AllEuriborRates> {
+    let text = decode_text(data);
+    let mut reader = ReaderBuilder::new()
+        .flexible(true)
+        .from_reader(text.as_bytes());
+    let headers = reader.headers()?.clone();
+    let column = |name: &str| headers.iter().position(|h| h.trim().eq_ignore_ascii_case(name));
+
+    let mut pending: BTreeMap<String, Pending> = BTreeMap::new();
+
+    if let (Some(key_col), Some(period_col), Some(value_col)) = (column("KEY"), column("TIME_PERIOD"), column("OBS_VALUE")) {
+        // SDMX-CSV: one observation per row
+        let status_col = column("OBS_STATUS");
+        let title_col = column("TITLE");
+        for result in reader.records() {
+            let record = result?;
+            let field = |i: usize| record.get(i).unwrap_or("");
+            let series = pending.entry(field(key_col).trim().to_string()).or_default();
+            if series.metadata.title.is_none() {
+                series.metadata.title = title_col.map(field).map(str::to_string).filter(|t| !t.is_empty());
+            }
+            series.raw.push(RawObservation {
+                date: parse_date(field(period_col))?,
+                value: parse_value(field(value_col)),
+                flag: status_col.and_then(|i| status_flag(field(i))),
+            });
+        }
+    } else if headers.get(0).is_some_and(|h| h.trim().eq_ignore_ascii_case("DATE")) {
+        // Portal download: one column per series, status columns mention "status"
+        let mut value_columns = Vec::new();
+        let mut status_columns = BTreeMap::new();
+        for (i, header) in headers.iter().enumerate().skip(1) {
+            if let Some((title, key)) = split_column_header(header) {
+                if title.to_lowercase().contains("status") {
+                    status_columns.insert(key, i);
+                } else {
+                    pending.entry(key.clone()).or_default().metadata.title = Some(title);
+                    value_columns.push((key, i));
+                }
+            }
+        }
+
+        let records: Vec<StringRecord> = reader.records().collect::<std::result::Result<_, _>>()?;
+        for (key, i) in &value_columns {
+            let series = pending.get_mut(key).expect("value column registered above");
+            for record in &records {
+                series.raw.push(RawObservation {
+                    date: parse_date(record.get(0).unwrap_or(""))?,
+                    value: parse_value(record.get(*i).unwrap_or("")),
+                    flag: status_columns.get(key).and_then(|&s| status_flag(record.get(s).unwrap_or(""))),
+                });
+            }
+        }
+    } else {
+        return Err(Error::Format("expected an ECB Data Portal CSV with a DATE or KEY/TIME_PERIOD/OBS_VALUE header".to_string()));
+    }
+
+    let mut all_rates = AllEuriborRates::new();
+    for (key, mut series) in pending {
+        let Some(tenor) = tenor_from_ecb_key(&key) else { continue };
+        series.raw.sort_by_key(|o| o.date);
+        let (rates, gaps) = resolve_missing(series.raw, options.missing_value_policy)?;
+        let metadata = SeriesMetadata {
+            series_key: Some(key),
+            source: Some(SOURCE_NAME.to_string()),
+            header_lines: 1,
+            ..series.metadata
+        };
+        all_rates.insert(tenor, Series { metadata, rates, gaps });
+    }
+
+    if all_rates.is_empty() {
+        return Err(Error::NoData("no Euribor series found in the ECB file".to_string()));
+    }
+    Ok(all_rates)
+}
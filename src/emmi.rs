@@ -0,0 +1,142 @@
+//! Importer for the historical Euribor files published by EMMI.
+//!
+//! EMMI's yearly files list one tenor per row with the fixing dates across
+//! the header (`,02/01/2015,05/01/2015,...` then `1w,0.018,...`); newer files
+//! list one date per row with the tenors across the header
+//! (`Date,1w,1m,3m,6m,12m`). Both orientations are accepted, as are several
+//! files covering consecutive years.
+
+use crate::archive::read_inputs;
+use crate::error::{Error, Result};
+use crate::loader::{decode_text, parse_date, LoadOptions};
+use crate::metadata::SeriesMetadata;
+use crate::missing::{resolve_missing, RawObservation, RawValue};
+use crate::rates::{AllEuriborRates, Series};
+use crate::source::RateSource;
+use crate::tenor::Tenor;
+use chrono::NaiveDate;
+use csv::{ReaderBuilder, StringRecord};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+const SOURCE_NAME: &str = "European Money Markets Institute (EMMI)";
+
+/// One or more EMMI historical rate files; gzipped files and ZIP archives of
+/// yearly files are read transparently.
+#[derive(Debug, Clone)]
+pub struct EmmiSource {
+    pub paths: Vec<PathBuf>,
+}
+
+impl EmmiSource {
+    pub fn new(paths: Vec<PathBuf>) -> Self {
+        EmmiSource { paths }
+    }
+}
+
+impl RateSource for EmmiSource {
+    fn name(&self) -> String {
+        format!("EMMI ({} files)", self.paths.len())
+    }
+
+    fn load(&self, options: &LoadOptions) -> Result<AllEuriborRates> {
+        let mut raw: BTreeMap<Tenor, Vec<RawObservation>> = BTreeMap::new();
+        for path in &self.paths {
+            for input in read_inputs(path).map_err(|e| Error::in_file(path, e))? {
+                collect_emmi_csv(&input.data, &mut raw).map_err(|e| Error::in_file(path, e))?;
+            }
+        }
+        build_rates(raw, options)
+    }
+}
+
+/// Tenor of an EMMI row or column label such as "1w", "3m", "1 week" or "12 months".
+pub fn tenor_from_label(label: &str) -> Option<Tenor> {
+    let label = label.trim().to_lowercase().replace(' ', "");
+    let digits: String = label.chars().take_while(char::is_ascii_digit).collect();
+    let count: u32 = digits.parse().ok().filter(|&count| count > 0)?;
+    match &label[digits.len()..] {
+        "w" | "week" | "weeks" => Some(Tenor::weeks(count)),
+        "m" | "month" | "months" => Some(Tenor::months(count)),
+        "y" | "year" | "years" => Some(Tenor::months(12 * count)),
+        _ => None,
+    }
+}
+
+// Parse an EMMI date, written as "DD/MM/YYYY" or in any form the Bundesbank loader accepts
+fn parse_emmi_date(value: &str) -> Result<NaiveDate> {
+    NaiveDate::parse_from_str(value.trim(), "%d/%m/%Y").or_else(|_| parse_date(value))
+}
+
+// Add the observations of one EMMI file to `raw`
+fn collect_emmi_csv(data: &[u8], raw: &mut BTreeMap<Tenor, Vec<RawObservation>>) -> Result<()> {
+    let text = decode_text(data);
+    let mut reader = ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .from_reader(text.as_bytes());
+    let records: Vec<StringRecord> = reader.records().collect::<std::result::Result<_, _>>()?;
+    let Some(header) = records.first() else {
+        return Err(Error::NoData("empty EMMI file".to_string()));
+    };
+
+    let observation = |date, field: &str| RawObservation { date, value: RawValue::parse(field, false), flag: None };
+    let header_dates: Vec<Option<NaiveDate>> = header.iter().skip(1).map(|h| parse_emmi_date(h).ok()).collect();
+
+    if !header_dates.is_empty() && header_dates.iter().all(Option::is_some) {
+        // One tenor per row, dates across the header
+        for record in &records[1..] {
+            let Some(tenor) = record.get(0).and_then(tenor_from_label) else { continue };
+            let series = raw.entry(tenor).or_default();
+            for (date, field) in header_dates.iter().flatten().zip(record.iter().skip(1)) {
+                series.push(observation(*date, field));
+            }
+        }
+    } else {
+        // One date per row, tenors across the header
+        let tenors: Vec<(usize, Tenor)> = header.iter().enumerate().skip(1)
+            .filter_map(|(i, label)| tenor_from_label(label).map(|t| (i, t)))
+            .collect();
+        if tenors.is_empty() {
+            return Err(Error::Format("no tenor labels found in the EMMI header".to_string()));
+        }
+        for record in &records[1..] {
+            let date = parse_emmi_date(record.get(0).unwrap_or(""))?;
+            for (i, tenor) in &tenors {
+                raw.entry(*tenor).or_default().push(observation(date, record.get(*i).unwrap_or("")));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+// Sort the collected observations and apply the missing-value policy
+fn build_rates(raw: BTreeMap<Tenor, Vec<RawObservation>>, options: &LoadOptions) -> Result<AllEuriborRates> {
+    let mut all_rates = AllEuriborRates::new();
+    for (tenor, mut observations) in raw {
+        observations.sort_by_key(|o| o.date);
+        observations.dedup_by_key(|o| o.date);
+        let (rates, gaps) = resolve_missing(observations, options.missing_value_policy)?;
+        let metadata = SeriesMetadata {
+            title: Some(format!("Euribor {}", tenor)),
+            source: Some(SOURCE_NAME.to_string()),
+            unit: Some("% p.a.".to_string()),
+            header_lines: 1,
+            ..SeriesMetadata::default()
+        };
+        all_rates.insert(tenor, Series { metadata, rates, gaps });
+    }
+
+    if all_rates.is_empty() {
+        return Err(Error::NoData("no Euribor tenors found in the EMMI files".to_string()));
+    }
+    Ok(all_rates)
+}
+
+/// Parse the contents of a single EMMI historical file into a rate collection.
+pub fn parse_emmi_csv(data: &[u8], options: &LoadOptions) -> Result<AllEuriborRates> {
+    let mut raw = BTreeMap::new();
+    collect_emmi_csv(data, &mut raw)?;
+    build_rates(raw, options)
+}
@@ -0,0 +1,119 @@
+//! Error type shared by the loader, the averaging engine and the chart builders.
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Errors returned by the library.
+#[derive(Debug)]
+pub enum Error {
+    /// Reading or writing a file failed.
+    Io(std::io::Error),
+    /// The CSV reader could not decode a record.
+    Csv(csv::Error),
+    /// A date field did not match the expected format.
+    Date { value: String, source: chrono::ParseError },
+    /// A ZIP archive could not be read.
+    Zip(zip::result::ZipError),
+    /// The rate store database reported an error.
+    Store(rusqlite::Error),
+    /// Serializing the chart data failed.
+    Json(serde_json::Error),
+    /// A series or rate collection contained no usable observations.
+    NoData(String),
+    /// A tenor code such as `M03` could not be parsed.
+    InvalidTenor(String),
+    /// A Bundesbank series key could not be parsed.
+    InvalidSeriesKey(String),
+    /// An option value such as a policy name was not recognised.
+    InvalidOption(String),
+    /// An observation had no usable value under [`MissingValuePolicy::Error`](crate::MissingValuePolicy::Error).
+    MissingValue { date: chrono::NaiveDate, value: String },
+    /// A download failed.
+    Http(String),
+    /// The layout of an input file was not recognised.
+    Format(String),
+    /// An error that occurred while processing a specific file.
+    InFile { path: PathBuf, source: Box<Error> },
+}
+
+impl Error {
+    /// Attach the path of the file being processed to an error.
+    pub fn in_file<P: AsRef<Path>>(path: P, error: Error) -> Error {
+        Error::InFile { path: path.as_ref().to_path_buf(), source: Box::new(error) }
+    }
+}
+
+/// Result alias using the library [`Error`].
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(e) => write!(f, "I/O error: {}", e),
+            Error::Csv(e) => write!(f, "CSV error: {}", e),
+            Error::Date { value, source } => write!(f, "invalid date '{}': {}", value, source),
+            Error::Zip(e) => write!(f, "ZIP error: {}", e),
+            Error::Store(e) => write!(f, "rate store error: {}", e),
+            Error::Json(e) => write!(f, "JSON error: {}", e),
+            Error::NoData(what) => write!(f, "no data: {}", what),
+            Error::InvalidTenor(code) => write!(f, "invalid tenor '{}'", code),
+            Error::InvalidSeriesKey(key) => write!(f, "invalid series key '{}'", key),
+            Error::InvalidOption(message) => f.write_str(message),
+            Error::MissingValue { date, value } => write!(f, "missing value on {}: '{}'", date, value),
+            Error::Http(message) => write!(f, "download failed: {}", message),
+            Error::Format(message) => write!(f, "unrecognised format: {}", message),
+            Error::InFile { path, source } => write!(f, "{}: {}", path.display(), source),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(e) => Some(e),
+            Error::Csv(e) => Some(e),
+            Error::Date { source, .. } => Some(source),
+            Error::Zip(e) => Some(e),
+            Error::Store(e) => Some(e),
+            Error::Json(e) => Some(e),
+            Error::InFile { source, .. } => Some(source.as_ref()),
+            Error::NoData(_)
+            | Error::InvalidTenor(_)
+            | Error::InvalidSeriesKey(_)
+            | Error::InvalidOption(_)
+            | Error::MissingValue { .. }
+            | Error::Http(_)
+            | Error::Format(_) => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::Io(e)
+    }
+}
+
+impl From<csv::Error> for Error {
+    fn from(e: csv::Error) -> Self {
+        Error::Csv(e)
+    }
+}
+
+impl From<zip::result::ZipError> for Error {
+    fn from(e: zip::result::ZipError) -> Self {
+        Error::Zip(e)
+    }
+}
+
+impl From<rusqlite::Error> for Error {
+    fn from(e: rusqlite::Error) -> Self {
+        Error::Store(e)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(e: serde_json::Error) -> Self {
+        Error::Json(e)
+    }
+}
@@ -0,0 +1,111 @@
+//! Download of Bundesbank series files.
+
+use crate::error::{Error, Result};
+use crate::loader::{parse_csv, LoadOptions};
+use crate::rates::EuriborRate;
+use crate::series_key::SeriesKey;
+use chrono::NaiveDate;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+/// Download endpoint of the Bundesbank time-series database.
+pub const DEFAULT_BASE_URL: &str = "https://api.statistiken.bundesbank.de/rest/download";
+
+/// URL of the English CSV export of a series below `base_url`.
+///
+/// The key `BBIG1.D.D0.EUR.MMKT.EURIBOR.M03.BID._Z` is requested as
+/// `{base_url}/BBIG1/D.D0.EUR.MMKT.EURIBOR.M03.BID._Z?format=csv&lang=en`.
+pub fn series_url(base_url: &str, key: &SeriesKey) -> String {
+    let key = key.to_string();
+    let (dataset, series) = key.split_once('.').unwrap_or((&key, ""));
+    format!("{}/{}/{}?format=csv&lang=en", base_url.trim_end_matches('/'), dataset, series)
+}
+
+/// How a fetched series compares with the local file it replaced.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FetchStatus {
+    /// There was no local file before.
+    New,
+    /// The observations are the same as in the local file.
+    Unchanged,
+    /// Observations were added, revised or removed.
+    Changed { added: usize, revised: usize, removed: usize },
+}
+
+/// Outcome of fetching one series.
+#[derive(Debug, Clone)]
+pub struct FetchResult {
+    pub key: SeriesKey,
+    pub path: PathBuf,
+    pub status: FetchStatus,
+    /// Number of observations in the downloaded file.
+    pub observations: usize,
+    /// Date of the last downloaded observation.
+    pub last_date: Option<NaiveDate>,
+}
+
+// Compare the observations of the previous and the downloaded file
+fn compare(old: &[EuriborRate], new: &[EuriborRate]) -> FetchStatus {
+    let old: BTreeMap<NaiveDate, f64> = old.iter().map(|r| (r.date, r.rate)).collect();
+    let new: BTreeMap<NaiveDate, f64> = new.iter().map(|r| (r.date, r.rate)).collect();
+
+    let added = new.keys().filter(|d| !old.contains_key(d)).count();
+    let removed = old.keys().filter(|d| !new.contains_key(d)).count();
+    let revised = new.iter().filter(|(d, r)| old.get(d).is_some_and(|o| o != *r)).count();
+
+    if added == 0 && revised == 0 && removed == 0 {
+        FetchStatus::Unchanged
+    } else {
+        FetchStatus::Changed { added, revised, removed }
+    }
+}
+
+// Download the body of a URL
+fn download(url: &str) -> Result<Vec<u8>> {
+    let response = ureq::get(url).call().map_err(|e| Error::Http(e.to_string()))?;
+    let mut body = Vec::new();
+    response.into_reader().read_to_end(&mut body)?;
+    Ok(body)
+}
+
+/// Download a series into `output_dir` and report how it changed.
+///
+/// The payload is parsed with the regular loader before it replaces the
+/// local file, so a failed or malformed download leaves the file untouched.
+pub fn fetch_series(base_url: &str, key: &SeriesKey, output_dir: &Path, options: &LoadOptions) -> Result<FetchResult> {
+    let payload = download(&series_url(base_url, key))?;
+    let series = parse_csv(&payload, options)?;
+    if series.rates.is_empty() {
+        return Err(Error::NoData(format!("the download of {} holds no observations", key)));
+    }
+
+    let path = output_dir.join(key.file_name());
+    let previous = fs::read(&path).ok();
+    let status = match &previous {
+        Some(previous) if *previous == payload => FetchStatus::Unchanged,
+        // A previous file the parser cannot read counts as replaced entirely
+        Some(previous) => match parse_csv(previous, options) {
+            Ok(old) => compare(&old.rates, &series.rates),
+            Err(_) => FetchStatus::Changed { added: series.rates.len(), revised: 0, removed: 0 },
+        },
+        None => FetchStatus::New,
+    };
+
+    // Write through a temporary file so an interrupted write keeps the old file
+    if previous.as_deref() != Some(payload.as_slice()) {
+        fs::create_dir_all(output_dir)?;
+        let partial = output_dir.join(format!("{}.part", key.file_name()));
+        fs::write(&partial, &payload)?;
+        fs::rename(&partial, &path)?;
+    }
+
+    Ok(FetchResult {
+        key: key.clone(),
+        path,
+        status,
+        observations: series.rates.len(),
+        last_date: series.rates.last().map(|r| r.date),
+    })
+}
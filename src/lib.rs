@@ -0,0 +1,54 @@
+//! Realized-cost analysis of Euribor tenors.
+//!
+//! The crate loads daily Euribor fixings exported by the Deutsche Bundesbank,
+//! the ECB Data Portal or EMMI (see [`RateSource`]), computes the average rate
+//! a borrower would have paid by rolling each tenor over a forward or trailing window, and
+//! renders the result as an interactive Plotly chart.
+
+pub mod archive;
+pub mod average;
+pub mod calendar;
+pub mod chart;
+pub mod day_count;
+pub mod discovery;
+pub mod ecb;
+pub mod emmi;
+pub mod error;
+pub mod fetch;
+pub mod loader;
+pub mod metadata;
+pub mod missing;
+pub mod projection;
+pub mod rates;
+pub mod scenario;
+pub mod schedule;
+pub mod series_key;
+pub mod source;
+pub mod store;
+pub mod tenor;
+pub mod validate;
+
+pub use average::{
+    calculate_average_rates, scenario_average_rates, AverageOptions, AverageRates, AveragingMethod,
+    Direction, FixingLookup, TailPolicy,
+};
+pub use calendar::{is_business_day, BusinessDayConvention};
+pub use chart::{create_chart_data, generate_html, ChartOptions, PageInfo};
+pub use day_count::DayCount;
+pub use discovery::{discover, load_discovered, Discovery};
+pub use ecb::EcbSource;
+pub use emmi::EmmiSource;
+pub use error::{Error, Result};
+pub use fetch::{fetch_series, FetchResult, FetchStatus};
+pub use loader::{parse_bulk_csv, parse_csv, read_bulk_csv, read_csv, CsvDialect, LoadOptions};
+pub use metadata::SeriesMetadata;
+pub use missing::{Gap, MissingValuePolicy};
+pub use projection::{DiscountCurve, Projection};
+pub use rates::{AllEuriborRates, EuriborRate, Series};
+pub use scenario::{Scenario, ScenarioKind, ScenarioPoint};
+pub use schedule::{reset_schedule, Anchor, ResetPeriod, ResetSchedule};
+pub use series_key::{Frequency, SeriesKey};
+pub use source::{BundesbankBulkSource, BundesbankSource, RateSource};
+pub use store::{ChangeKind, ImportSummary, ObservationChange, RateStore, StoreSource, Vintage};
+pub use tenor::Tenor;
+pub use validate::{validate, Issue, IssueKind, ValidationOptions};
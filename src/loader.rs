@@ -0,0 +1,238 @@
+//! Reader for Bundesbank Euribor CSV exports.
+
+use crate::archive::{read_inputs, read_single_input};
+use crate::error::{Error, Result};
+use crate::metadata::SeriesMetadata;
+use crate::missing::{resolve_missing, MissingValuePolicy, RawObservation, RawValue};
+use crate::rates::{AllEuriborRates, Series};
+use crate::series_key::SeriesKey;
+use chrono::NaiveDate;
+use csv::{ReaderBuilder, StringRecord};
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+/// Layout variant of a Bundesbank CSV export.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CsvDialect {
+    /// English pages: `,` separators and decimal points.
+    English,
+    /// German pages: `;` separators and decimal commas.
+    German,
+}
+
+impl CsvDialect {
+    /// Guess the dialect from the first line of a file.
+    pub fn detect(text: &str) -> CsvDialect {
+        let first_line = text.lines().next().unwrap_or("");
+        if first_line.matches(';').count() > first_line.matches(',').count() {
+            CsvDialect::German
+        } else {
+            CsvDialect::English
+        }
+    }
+
+    pub fn delimiter(&self) -> u8 {
+        match self {
+            CsvDialect::English => b',',
+            CsvDialect::German => b';',
+        }
+    }
+
+    pub fn decimal_comma(&self) -> bool {
+        *self == CsvDialect::German
+    }
+}
+
+impl fmt::Display for CsvDialect {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            CsvDialect::English => "en",
+            CsvDialect::German => "de",
+        })
+    }
+}
+
+impl FromStr for CsvDialect {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "en" | "english" => Ok(CsvDialect::English),
+            "de" | "german" => Ok(CsvDialect::German),
+            _ => Err(Error::InvalidOption(format!("unknown CSV dialect '{}'", s))),
+        }
+    }
+}
+
+// Parse an observation date in ISO ("YYYY-MM-DD") or German ("DD.MM.YYYY") form;
+// monthly series use "YYYY-MM" or "MM.YYYY" and map to the first of the month
+pub(crate) fn parse_date(value: &str) -> Result<NaiveDate> {
+    let value = value.trim();
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .or_else(|e| NaiveDate::parse_from_str(value, "%d.%m.%Y").map_err(|_| e))
+        .or_else(|e| NaiveDate::parse_from_str(&format!("{}-01", value), "%Y-%m-%d").map_err(|_| e))
+        .or_else(|e| NaiveDate::parse_from_str(&format!("01.{}", value), "%d.%m.%Y").map_err(|_| e))
+        .map_err(|e| Error::Date { value: value.to_string(), source: e })
+}
+
+/// Decode file contents as UTF-8, falling back to Latin-1 for German exports.
+pub fn decode_text(bytes: &[u8]) -> String {
+    let bytes = bytes.strip_prefix(b"\xef\xbb\xbf").unwrap_or(bytes);
+    match std::str::from_utf8(bytes) {
+        Ok(text) => text.to_string(),
+        Err(_) => bytes.iter().map(|&b| b as char).collect(),
+    }
+}
+
+/// Options applied while loading a series.
+#[derive(Debug, Clone, Default)]
+pub struct LoadOptions {
+    pub missing_value_policy: MissingValuePolicy,
+    /// CSV dialect of the input; detected from the file when `None`.
+    pub dialect: Option<CsvDialect>,
+}
+
+/// Read a Bundesbank CSV file and return its header metadata and fixings in file order.
+///
+/// The file may be gzipped (`.csv.gz`) or a ZIP archive holding a single
+/// series. See [`parse_csv`] for the accepted layout.
+pub fn read_csv<P: AsRef<Path>>(path: P, options: &LoadOptions) -> Result<Series> {
+    parse_csv(&read_single_input(path.as_ref())?.data, options)
+}
+
+/// Parse the contents of a Bundesbank CSV export holding a single series.
+///
+/// Both the English and the German variant are accepted. The metadata header
+/// ends at the first line starting with a date. The flag/comment column next
+/// to each value is kept on the observation. Missing values (".", empty,
+/// "No value available" or unparsable) are handled according to the
+/// missing-value policy and listed in the gap report. If the file holds
+/// several series, the first one is returned.
+pub fn parse_csv(data: &[u8], options: &LoadOptions) -> Result<Series> {
+    parse_bulk_csv(data, options)?
+        .into_iter()
+        .next()
+        .ok_or_else(|| Error::NoData("no value column found".to_string()))
+}
+
+// Value and flag columns of one series and the data collected for it
+struct Column {
+    value: usize,
+    flags: Option<usize>,
+    metadata: SeriesMetadata,
+    raw: Vec<RawObservation>,
+}
+
+impl Column {
+    fn new(value: usize) -> Self {
+        Column { value, flags: None, metadata: SeriesMetadata::default(), raw: Vec::new() }
+    }
+}
+
+// Find the series columns from the first header line ",KEY1,KEY1_FLAGS,KEY2,KEY2_FLAGS,..."
+fn find_columns(first_header: Option<&StringRecord>) -> Vec<Column> {
+    let mut columns: Vec<Column> = Vec::new();
+    for (i, cell) in first_header.into_iter().flat_map(|r| r.iter().enumerate().skip(1)) {
+        let cell = cell.trim();
+        if cell.to_uppercase().ends_with("_FLAGS") {
+            if let Some(column) = columns.last_mut().filter(|c| c.flags.is_none()) {
+                column.flags = Some(i);
+            }
+        } else if !cell.is_empty() {
+            columns.push(Column::new(i));
+        }
+    }
+
+    // Without a key line, assume a single series with its flags next to it
+    if columns.is_empty() {
+        let mut column = Column::new(1);
+        column.flags = Some(2);
+        columns.push(column);
+    }
+    columns
+}
+
+/// Parse a Bundesbank CSV export holding one or more series side by side.
+///
+/// Wide exports from the time-series database have a date column followed
+/// by a value and a flag column per series, with the series keys in the
+/// first header line. Series are returned in column order.
+pub fn parse_bulk_csv(data: &[u8], options: &LoadOptions) -> Result<Vec<Series>> {
+    let text = decode_text(data);
+    let dialect = options.dialect.unwrap_or_else(|| CsvDialect::detect(&text));
+    let mut reader = ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .delimiter(dialect.delimiter())
+        .from_reader(text.as_bytes());
+
+    let mut header = Vec::new();
+    let mut columns: Option<Vec<Column>> = None;
+
+    for result in reader.records() {
+        let record = result?;
+
+        // Header lines continue until the first line that starts with a date
+        let columns = match &mut columns {
+            Some(columns) => columns,
+            None => {
+                if parse_date(record.get(0).unwrap_or("")).is_err() {
+                    header.push(record);
+                    continue;
+                }
+                let mut found = find_columns(header.first());
+                for column in &mut found {
+                    for header_record in &header {
+                        column.metadata.apply_header_record(header_record, column.value);
+                    }
+                }
+                columns.insert(found)
+            }
+        };
+
+        if record.len() < 2 {
+            continue;
+        }
+
+        let date = parse_date(&record[0])?;
+        for column in columns.iter_mut() {
+            let value = RawValue::parse(record.get(column.value).unwrap_or(""), dialect.decimal_comma());
+            let flag = column.flags
+                .and_then(|i| record.get(i))
+                .map(str::trim)
+                .filter(|f| !f.is_empty())
+                .map(str::to_string);
+            column.raw.push(RawObservation { date, value, flag });
+        }
+    }
+
+    columns.unwrap_or_default()
+        .into_iter()
+        .map(|column| {
+            let (rates, gaps) = resolve_missing(column.raw, options.missing_value_policy)?;
+            Ok(Series { metadata: column.metadata, rates, gaps })
+        })
+        .collect()
+}
+
+/// Read a wide Bundesbank export and file every Euribor column under its tenor.
+///
+/// Columns are matched to tenors through the series key in their header;
+/// columns of other series are skipped.
+pub fn read_bulk_csv<P: AsRef<Path>>(path: P, options: &LoadOptions) -> Result<AllEuriborRates> {
+    let mut all_rates = AllEuriborRates::new();
+    for input in read_inputs(path.as_ref())? {
+        for series in parse_bulk_csv(&input.data, options)? {
+            let key = series.metadata.series_key.as_deref().and_then(|k| k.parse::<SeriesKey>().ok());
+            if let Some(key) = key.filter(SeriesKey::is_euribor) {
+                all_rates.insert(key.tenor, series);
+            }
+        }
+    }
+
+    if all_rates.is_empty() {
+        return Err(Error::NoData("no Euribor series columns found".to_string()));
+    }
+    Ok(all_rates)
+}
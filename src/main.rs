@@ -1,6 +1,11 @@
 // Download daily historical Euribor rate data from 
 // https://www.bundesbank.de/en/statistics/money-and-capital-markets/interest-rates-and-yields/money-market-rates-651538
-// and store the files in the directory of Cargo.toml as:
+// or let the program do it with
+//    cargo run fetch [--output-dir <dir>] [--base-url <url>] [--tenors ...]
+// which downloads the series from the Bundesbank (or the mirror at <url>),
+// checks each download with the CSV parser before replacing the local file,
+// and reports which series changed since the last fetch. The files are
+// stored in the directory of Cargo.toml (or <dir>) as:
 //    "BBIG1.D.D0.EUR.MMKT.EURIBOR.W01.BID._Z.csv"
 //    "BBIG1.D.D0.EUR.MMKT.EURIBOR.M01.BID._Z.csv"
 //    "BBIG1.D.D0.EUR.MMKT.EURIBOR.M03.BID._Z.csv"
@@ -8,317 +13,582 @@
 //    "BBIG1.D.D0.EUR.MMKT.EURIBOR.M12.BID._Z.csv"
 //
 // Then run this program with:
-//    cargo run 'days'
-// where 'days' is the number of days for the forward average rate.
+//    cargo run 'days' [--tenors W01,M01,M03,M06,M12]
+// where 'days' is the number of days for the forward average rate (or a
+// comma-separated list such as 360,1080,1800, selectable on the chart page) and
+// --tenors optionally selects the tenors to load. Pass --trailing to chart
+// the cost over the window ending on each date instead of the one starting on it.
+// Near the end of the data (the start, when trailing) the windows are
+// incomplete; --tail partial (default) draws those averages dashed over a
+// shaded area, --tail truncate leaves them out, and --tail project completes
+// forward windows with projected fixings: the last fixing of each tenor, or
+// with --projection forward-curve the forward rates implied by the latest
+// 1w-12m fixings. Rate scenarios are charted next to the history with
+// --scenario <file> (repeat for several): a CSV with the columns
+// date,tenor,rate (percent) or date,tenor,shift_bp (a parallel shift of the
+// last fixings), where an empty tenor applies to all tenors. Rates move
+// linearly between the dates and each scenario completes the forward
+// windows as a separate trace named after its file. Discontinued tenors
+// (W02, W03, M02, M04, ..., M11) can be loaded if their files are present.
+//
+// Alternatively, let the program find the files itself:
+//    cargo run 'days' --input-dir <dir> [--frequency D|M]
+// which loads every Euribor series file in <dir> that is named after its
+// Bundesbank series key (plain .csv, gzipped .csv.gz, or inside a .zip such
+// as the Bundesbank bulk download), reports the files it did not recognise,
+// and charts the series of the selected frequency (daily by default).
+//
+// A wide Bundesbank export holding several series side by side is read with
+// --bulk <file>.
+//
+// Instead of Bundesbank files, Euribor can be read from an ECB Data Portal
+// download with --ecb <file>, or from EMMI's historical files with
+// --emmi <file> (repeat for several years).
+//
+// Observations can be kept in a local SQLite store:
+//    cargo run import --store <file> [input options]
+// merges the input selected with the options above into the store, and
+//    cargo run 'days' --store <file> [--as-of <vintage>]
+// charts the stored observations instead of reading the CSV files. Every
+// import is kept as a vintage; --as-of charts the store as it was after an
+// import, given by its id or a date (the last import on or before it).
+//    cargo run vintages --store <file>
+// lists the imports, and
+//    cargo run diff --store <file> --from <vintage> [--to <vintage>]
+// lists the observations added, removed or changed between two vintages
+// (the latest one by default).
+//
+// A reset date is resolved to the last fixing on or before it by default;
+// --fixing following|modified-following|preceding first moves it to a TARGET
+// business day, and --fixing t-2 uses the fixing two business days earlier.
+// If no fixing was published on the date looked up, the last one before it
+// applies.
+//
+// Tenors roll over on calendar months (1m, 3m, 6m, 12m) or weeks, and the
+// interest of each period accrues under --day-count act/360|act/365|30/360
+// (ACT/360 by default, as for Euribor loans). The averages are the interest
+// divided by the accrued year fractions, so a constant fixing averages to
+// itself; --per-365-days divides it by actual years of 365 days instead,
+// charting the effective annual cost. Pass --method compounded to reinvest
+// the interest at every reset and chart the annualised rollover rate instead
+// of the simple mean.
+//
+// The cost of one specific loan is shown with
+//    cargo run schedule --anchor <date|month-day|day> [--start <date>] [--end <date>] [--tenors M12]
+// which lists the resets of a loan starting on <date> (or resetting every
+// year on <month-day> such as 03-15, or on <day> of the month, since before
+// the start of the window), the fixing used at each reset and the average
+// rate paid from --start to --end (the whole data by default).
+//
+// Check the selected input with
+//    cargo run validate [input options] [--rate-range <min>,<max>] [--max-jump <pp>] [--max-gap <days>]
+// which reports duplicate or out-of-order dates, fixings on weekends and
+// TARGET holidays, day-over-day jumps above <pp> percentage points (0.5),
+// rates outside <min>..<max> percent (-2..20), and runs of more than <days>
+// days (10) without a fixing in one tenor while other tenors have data.
+// Missing values are not filled for validation. The program exits with an
+// error if any problem is found.
+//
+// Files downloaded from the German Bundesbank pages (";" separators, decimal
+// commas, DD.MM.YYYY dates, Latin-1 text) are detected automatically; force a
+// dialect with --dialect en|de.
+//
+// Missing values are forward-filled by default; choose another policy with
+// --missing drop|forward-fill|interpolate|error, and pass --gap-report to
+// list every gap per tenor. import and validate always keep the gaps and
+// reject --missing.
+//
+// Pass --highlight-flags to mark flagged observations (e.g. provisional
+// values) on the daily-value traces, and --business-days to leave weekends
+// and TARGET holidays out of the date axis.
 //
 // The program will create a file "euribor_cost_chart.html".
 
-use chrono::{NaiveDate, Duration};
-use csv::ReaderBuilder;
-use serde_json::json;
-use std::collections::HashMap;
+use euribor_cost_chart::{
+    calculate_average_rates, create_chart_data, discover, fetch::DEFAULT_BASE_URL, fetch_series,
+    generate_html, load_discovered, reset_schedule, scenario_average_rates, validate,
+    AllEuriborRates, Anchor, AverageOptions, BundesbankBulkSource, BundesbankSource, ChangeKind,
+    ChartOptions, Direction, EcbSource, EmmiSource, FetchStatus, Frequency, LoadOptions,
+    MissingValuePolicy, PageInfo, RateSource, RateStore, Scenario, Series, SeriesKey, StoreSource,
+    Tenor, ValidationOptions, Vintage,
+};
+use chrono::NaiveDate;
 use std::env;
 use std::error::Error;
 use std::fs::File;
 use std::io::Write;
-
-const NUM_RATES: usize = 5;
-
-#[derive(Debug, Clone)]
-struct EuriborRate {
-    date: NaiveDate,
-    rate: f64,
+use std::path::PathBuf;
+
+// Action selected on the command line
+#[derive(PartialEq)]
+enum Command {
+    Chart,
+    Fetch,
+    Import,
+    Vintages,
+    Diff,
+    Validate,
+    Schedule,
 }
 
-#[derive(Debug)]
-struct AllEuriborRates {
-    w01: Vec<EuriborRate>,
-    m01: Vec<EuriborRate>,
-    m03: Vec<EuriborRate>,
-    m06: Vec<EuriborRate>,
-    m12: Vec<EuriborRate>,
+// Command line options
+struct Options {
+    command: Command,
+    averaged_time_days: Vec<i64>,
+    tenors: Option<Vec<Tenor>>,
+    input_dir: Option<PathBuf>,
+    bulk: Option<PathBuf>,
+    ecb: Option<PathBuf>,
+    emmi: Vec<PathBuf>,
+    frequency: Frequency,
+    load: LoadOptions,
+    gap_report: bool,
+    average: AverageOptions,
+    chart: ChartOptions,
+    scenarios: Vec<PathBuf>,
+    output_dir: Option<PathBuf>,
+    base_url: String,
+    store: Option<PathBuf>,
+    as_of: Vintage,
+    diff_from: Option<Vintage>,
+    validation: ValidationOptions,
+    anchor: Option<Anchor>,
+    window_start: Option<NaiveDate>,
+    window_end: Option<NaiveDate>,
 }
 
-// Read a CSV file and return a vector of EuriborRate structs
-fn read_csv(path: &str) -> Result<Vec<EuriborRate>, Box<dyn Error>> {
-    let file = File::open(path)?;
-    let mut reader = ReaderBuilder::new()
-        .has_headers(false)
-        .flexible(true)
-        .from_reader(file);
-
-    let mut rates = Vec::new();
-    let mut records = reader.records();
-    let mut last_valid_rate: Option<f64> = None;
-
-    // Skip the first 9 lines (metadata)
-    for _ in 0..9 {
-        records.next();
-    }
+// Parse the command line arguments, falling back to defaults where not given
+fn parse_args() -> Result<Options, Box<dyn Error>> {
+    let mut options = Options {
+        command: Command::Chart,
+        averaged_time_days: vec![360],
+        tenors: None,
+        input_dir: None,
+        bulk: None,
+        ecb: None,
+        emmi: Vec::new(),
+        frequency: Frequency::Daily,
+        load: LoadOptions::default(),
+        gap_report: false,
+        average: AverageOptions::default(),
+        chart: ChartOptions::default(),
+        scenarios: Vec::new(),
+        output_dir: None,
+        base_url: DEFAULT_BASE_URL.to_string(),
+        store: None,
+        as_of: Vintage::Latest,
+        diff_from: None,
+        validation: ValidationOptions::default(),
+        anchor: None,
+        window_start: None,
+        window_end: None,
+    };
 
-    for result in records {
-        let record = result?;
-        if record.len() < 2 {
-            continue;
-        }
-        
-        let date = NaiveDate::parse_from_str(&record[0], "%Y-%m-%d")?;
-        let rate_str = record[1].trim();
-        
-        let rate = if rate_str == "." || rate_str.is_empty() || rate_str.to_lowercase().contains("no value") {
-            last_valid_rate
-        } else {
-            match rate_str.parse::<f64>() {
-                Ok(r) => {
-                    last_valid_rate = Some(r);
-                    Some(r)
-                }
-                Err(_) => last_valid_rate
+    // import and validate keep gaps, so an explicit policy would be ignored
+    let mut missing_given = false;
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--tenors" => {
+                let list = args.next().ok_or("--tenors requires a comma-separated list")?;
+                options.tenors = Some(list.split(',')
+                    .map(|code| code.parse())
+                    .collect::<Result<_, _>>()?);
+            }
+            "--input-dir" => {
+                options.input_dir = Some(args.next().ok_or("--input-dir requires a directory")?.into());
+            }
+            "--bulk" => {
+                options.bulk = Some(args.next().ok_or("--bulk requires a file")?.into());
+            }
+            "--ecb" => {
+                options.ecb = Some(args.next().ok_or("--ecb requires a file")?.into());
+            }
+            "--emmi" => {
+                options.emmi.push(args.next().ok_or("--emmi requires a file")?.into());
+            }
+            "--frequency" => {
+                options.frequency = args.next().ok_or("--frequency requires D or M")?.parse()?;
+            }
+            "--missing" => {
+                missing_given = true;
+                options.load.missing_value_policy = args.next()
+                    .ok_or("--missing requires drop, forward-fill, interpolate or error")?
+                    .parse()?;
+            }
+            "--dialect" => {
+                options.load.dialect = Some(args.next().ok_or("--dialect requires en or de")?.parse()?);
+            }
+            "--fixing" => {
+                options.average.fixing_lookup = args.next()
+                    .ok_or("--fixing requires previous, following, modified-following, preceding or t-<days>")?
+                    .parse()?;
+            }
+            "--day-count" => {
+                options.average.day_count = args.next()
+                    .ok_or("--day-count requires act/360, act/365 or 30/360")?
+                    .parse()?;
+            }
+            "--method" => {
+                options.average.method = args.next().ok_or("--method requires simple or compounded")?.parse()?;
+            }
+            "--per-365-days" => options.average.per_actual_year = true,
+            "--tail" => {
+                options.average.tail = args.next().ok_or("--tail requires partial, truncate or project")?.parse()?;
+            }
+            "--projection" => {
+                options.average.projection = args.next().ok_or("--projection requires flat or forward-curve")?.parse()?;
+            }
+            "--scenario" => {
+                options.scenarios.push(args.next().ok_or("--scenario requires a file")?.into());
+            }
+            "--trailing" => options.average.direction = Direction::Trailing,
+            "--gap-report" => options.gap_report = true,
+            "--highlight-flags" => options.chart.highlight_flags = true,
+            "--business-days" => options.chart.business_days_axis = true,
+            "--output-dir" => {
+                options.output_dir = Some(args.next().ok_or("--output-dir requires a directory")?.into());
+            }
+            "--base-url" => {
+                options.base_url = args.next().ok_or("--base-url requires a URL")?;
+            }
+            "--store" => {
+                options.store = Some(args.next().ok_or("--store requires a file")?.into());
+            }
+            "--as-of" | "--to" => {
+                options.as_of = args.next().ok_or("--as-of and --to require an import id or a date")?.parse()?;
+            }
+            "--from" => {
+                options.diff_from = Some(args.next().ok_or("--from requires an import id or a date")?.parse()?);
+            }
+            "--rate-range" => {
+                let range = args.next().ok_or("--rate-range requires <min>,<max>")?;
+                let (min, max) = range.split_once(',').ok_or("--rate-range requires <min>,<max>")?;
+                options.validation.min_rate = min.trim().parse()?;
+                options.validation.max_rate = max.trim().parse()?;
+            }
+            "--max-jump" => {
+                options.validation.max_jump = args.next().ok_or("--max-jump requires percentage points")?.parse()?;
+            }
+            "--max-gap" => {
+                options.validation.max_gap_days = args.next().ok_or("--max-gap requires a number of days")?.parse()?;
+            }
+            "--anchor" => {
+                options.anchor = Some(args.next().ok_or("--anchor requires a date, a month and day (MM-DD) or a day of the month")?.parse()?);
+            }
+            "--start" => {
+                let date = args.next().ok_or("--start requires a date")?;
+                options.window_start = Some(NaiveDate::parse_from_str(&date, "%Y-%m-%d")?);
+            }
+            "--end" => {
+                let date = args.next().ok_or("--end requires a date")?;
+                options.window_end = Some(NaiveDate::parse_from_str(&date, "%Y-%m-%d")?);
+            }
+            "fetch" => options.command = Command::Fetch,
+            "import" => options.command = Command::Import,
+            "vintages" => options.command = Command::Vintages,
+            "diff" => options.command = Command::Diff,
+            "validate" => options.command = Command::Validate,
+            "schedule" => options.command = Command::Schedule,
+            _ if arg.starts_with("--") => return Err(format!("unknown option '{}'", arg).into()),
+            _ => {
+                options.averaged_time_days = arg.split(',')
+                    .map(|days| days.trim().parse::<i64>().ok().filter(|&days| days > 0))
+                    .collect::<Option<_>>()
+                    .ok_or_else(|| format!("expected the number of days (or a comma-separated list), got '{}'", arg))?;
             }
-        };
-        
-        if let Some(r) = rate {
-            rates.push(EuriborRate { date, rate: r });
         }
     }
 
-    Ok(rates)
+    if missing_given && matches!(options.command, Command::Import | Command::Validate) {
+        return Err("--missing cannot be used with import or validate, which keep gaps as they are".into());
+    }
+    Ok(options)
 }
 
-// Calculate average rates and determine the averaged time mark
-fn calculate_average_rates(all_rates: &AllEuriborRates, averaged_time_days: i64) -> (Vec<[f64; NUM_RATES]>, NaiveDate) {
-    let rates_vec = [
-        &all_rates.w01, &all_rates.m01, &all_rates.m03,
-        &all_rates.m06, &all_rates.m12
-    ];
-    let periods = [7, 30, 90, 180, 360];
-
-    let start_date = rates_vec.iter()
-        .filter_map(|r| r.first())
-        .min_by_key(|r| r.date)
-        .map(|r| r.date)
-        .unwrap();
-    let end_date = rates_vec.iter()
-        .filter_map(|r| r.last())
-        .max_by_key(|r| r.date)
-        .map(|r| r.date)
-        .unwrap();
-
-    let rate_maps: Vec<HashMap<NaiveDate, f64>> = rates_vec.iter()
-        .map(|rates| rates.iter().map(|r| (r.date, r.rate)).collect())
-        .collect();
-
-    let mut averages = Vec::new();
-    let mut current_date = start_date;
-    let averaged_time_mark = end_date - Duration::days(averaged_time_days);
-
-    while current_date <= end_date {
-        let mut avg_rates = [0.0; NUM_RATES];
-
-        for i in 0..NUM_RATES {
-            let period = periods[i];
-            let mut sum = 0.0;
-            let mut total_days = 0;
-            let mut check_date = current_date;
-            let days_left = (end_date - current_date).num_days() + 1;
-            let check_period = std::cmp::min(averaged_time_days, days_left as i64);
-
-            while check_date <= current_date + Duration::days(check_period - 1) {
-                if let Some(&rate) = rate_maps[i].get(&check_date) {
-                    let days_in_period = std::cmp::min(period, (end_date - check_date).num_days() as i64 + 1);
-                    sum += rate * days_in_period as f64;
-                    total_days += days_in_period;
-                }
-                check_date += Duration::days(period);
+// Print the metadata, the record and gap counts and the first and last record of a loaded series
+fn print_summary(series: &Series, gap_report: bool) {
+    let metadata = &series.metadata;
+    let rates = &series.rates;
+    if let Some(title) = &metadata.title {
+        println!("Title: {}", title);
+    }
+    if let Some(unit) = &metadata.unit {
+        match &metadata.unit_multiplier {
+            Some(multiplier) => println!("Unit: {} (multiplier: {})", unit, multiplier),
+            None => println!("Unit: {}", unit),
+        }
+    }
+    if let Some(last_update) = metadata.last_update {
+        println!("Last update: {}", last_update);
+    }
+    if let Some(source) = &metadata.source {
+        println!("Source: {}", source);
+    }
+    for note in &metadata.notes {
+        println!("Note: {}", note);
+    }
+    println!("Header lines: {}", metadata.header_lines);
+    println!("Total records: {}", rates.len());
+    println!("Flagged records: {} ({} provisional)", series.flagged_count(), series.provisional_count());
+    let unparsable: usize = series.gaps.iter().map(|g| g.unparsable.len()).sum();
+    println!("Gaps: {} ({} records filled, {} unparsable values)", series.gaps.len(), series.filled_count(), unparsable);
+    if gap_report {
+        for gap in &series.gaps {
+            println!(" {} to {}: {} missing, {}", gap.start, gap.end, gap.missing,
+                if gap.filled { "filled" } else { "dropped" });
+            for (date, text) in &gap.unparsable {
+                println!("  Unparsable value on {}: '{}'", date, text);
             }
-
-            avg_rates[i] = if total_days > 0 { sum / total_days as f64 } else { 0.0 };
         }
+    }
+    println!("First record:");
+    for rate in rates.iter().take(1) {
+        println!(" Date: {}, Rate: {}", rate.date, rate.rate);
+    }
+    println!("Last record:");
+    for rate in rates.iter().rev().take(1) {
+        println!(" Date: {}, Rate: {}", rate.date, rate.rate);
+    }
+    println!();
+}
+
+// Load the series of a rate source and print their summaries
+fn read_source(source: &dyn RateSource, options: &Options) -> Result<AllEuriborRates, Box<dyn Error>> {
+    println!("Reading {}...\n", source.name());
+    let mut all_rates = source.load(&options.load)?;
+    if let Some(tenors) = &options.tenors {
+        all_rates.retain(|tenor| tenors.contains(&tenor));
+    }
 
-        averages.push(avg_rates);
-        current_date += Duration::days(1);
+    for (tenor, series) in all_rates.iter_series() {
+        println!("Loaded {}:", tenor);
+        print_summary(series, options.gap_report);
+        if series.rates.is_empty() {
+            return Err(format!("No valid rates found for the {} series", tenor).into());
+        }
     }
 
-    (averages, averaged_time_mark)
+    Ok(all_rates)
 }
 
-// Create the chart data for Plotly
-fn create_chart_data(all_rates: &AllEuriborRates, averages: &[[f64; NUM_RATES]], averaged_time_mark: NaiveDate, averaged_time_days: i64) -> Result<serde_json::Value, Box<dyn Error>> {
-    let labels = ["1w", "1m", "3m", "6m", "12m"];
-    let colors = ["#1f77b4", "#ff7f0e", "#2ca02c", "#d62728", "#9467bd"];
-    let mut traces = Vec::new();
-
-    let rates_vec = [
-        &all_rates.w01, &all_rates.m01, &all_rates.m03,
-        &all_rates.m06, &all_rates.m12
-    ];
-
-    let start_date = rates_vec.iter()
-        .filter_map(|r| r.first())
-        .min_by_key(|r| r.date)
-        .map(|r| r.date)
-        .unwrap();
-
-    for i in 0..NUM_RATES {
-        // Average rates trace
-        let avg_trace = json!({
-            "x": (0..averages.len()).map(|j| (start_date + Duration::days(j as i64)).format("%Y-%m-%d").to_string()).collect::<Vec<String>>(),
-            "y": averages.iter().map(|a| a[i]).collect::<Vec<f64>>(),
-            "type": "scattergl",
-            "mode": "lines",
-            "name": format!("{} ({}d rlz avg)", labels[i], averaged_time_days),
-            "line": {
-                "color": colors[i],
-                "width": 2
-            }
-        });
-        traces.push(avg_trace);
-
-        // Daily rates trace
-        let daily_trace = json!({
-            "x": rates_vec[i].iter().map(|r| r.date.format("%Y-%m-%d").to_string()).collect::<Vec<String>>(),
-            "y": rates_vec[i].iter().map(|r| r.rate).collect::<Vec<f64>>(),
-            "type": "scattergl",
-            "mode": "lines",
-            "name": format!("{} (daily value)", labels[i]),
-            "line": {
-                "color": colors[i],
-                "width": 1,
-                "dash": "dot"
+// Discover and read every Euribor series file in a directory
+fn read_input_dir(options: &Options, input_dir: &PathBuf) -> Result<AllEuriborRates, Box<dyn Error>> {
+    println!("Scanning {}...\n", input_dir.display());
+    let mut discovery = discover(input_dir)?;
+    if let Some(tenors) = &options.tenors {
+        discovery.files.retain(|f| tenors.contains(&f.key.tenor));
+    }
+
+    for file in &discovery.files {
+        println!("Found {} ({} {})", file.location(), file.key.frequency, file.key.tenor);
+    }
+    for path in &discovery.unrecognised {
+        println!("Skipping unrecognised file {}", path.display());
+    }
+    println!();
+
+    let mut loaded = load_discovered(&discovery, &options.load)?;
+    for (frequency, all_rates) in &loaded {
+        for (tenor, series) in all_rates.iter_series() {
+            println!("Loaded {} {}:", frequency, tenor);
+            print_summary(series, options.gap_report);
+            if series.rates.is_empty() {
+                return Err(format!("No valid rates found for the {} {} series", frequency, tenor).into());
             }
-        });
-        traces.push(daily_trace);
+        }
     }
 
-    // Add vertical line for the averaged time mark
-    let max_rate = rates_vec.iter()
-        .flat_map(|rates| rates.iter().map(|r| r.rate))
-        .fold(f64::NEG_INFINITY, f64::max);
-
-    let vertical_line = json!({
-        "x": [averaged_time_mark.format("%Y-%m-%d").to_string(), averaged_time_mark.format("%Y-%m-%d").to_string()],
-        "y": [0, max_rate],
-        "type": "scatter",
-        "mode": "lines",
-        "name": "Full forward data end point",
-        "line": {
-            "color": "gray",
-            "width": 1,
-            "dash": "dash"
-        },
-        "showlegend": true
-    });
-    traces.push(vertical_line);
-
-    Ok(json!(traces))
+    let available: Vec<String> = loaded.keys().map(|f| f.to_string()).collect();
+    loaded.remove(&options.frequency).ok_or_else(|| format!(
+        "No {} Euribor series found in {} (available: {})",
+        options.frequency, input_dir.display(),
+        if available.is_empty() { "none".to_string() } else { available.join(", ") },
+    ).into())
 }
 
-// Generate the HTML content for the chart
-fn generate_html(chart_data: &serde_json::Value, averaged_time_days: i64) -> String {
-    format!(r#"
-<!DOCTYPE html>
-<html>
-<head>
-    <title>Euribor Rates Chart</title>
-    <script src="https://cdn.plot.ly/plotly-latest.min.js"></script>
-    <style>
-        #chart {{ width: 100%; height: 800px; }}
-    </style>
-</head>
-<body>
-    <div id="chart"></div>
-    <script>
-        var data = {0};
-        var layout = {{
-            title: 'Euribor rates\' {1}-day forward realized cost (average interest rate)',
-            showlegend: true,
-            xaxis: {{ 
-                title: 'Date', 
-                type: 'date',
-                rangeslider: {{visible: true}}
-            }},
-            yaxis: {{ 
-                title: 'Interest rate (%)',
-                dtick: 0.5
-            }},
-            dragmode: 'zoom'
-        }};
-        var config = {{
-            scrollZoom: true,
-            modeBarButtonsToAdd: ['drawline', 'drawopenpath', 'drawclosedpath', 'drawcircle', 'drawrect', 'eraseshape']
-        }};
-        
-        Plotly.newPlot('chart', data, layout, config);
-    </script>
-</body>
-</html>
-    "#, chart_data, averaged_time_days)
+// Download the selected series and report what changed
+fn run_fetch(options: &Options) -> Result<(), Box<dyn Error>> {
+    let output_dir = options.output_dir.clone()
+        .or_else(|| options.input_dir.clone())
+        .unwrap_or_else(|| PathBuf::from("."));
+    let tenors = options.tenors.as_deref().unwrap_or(&Tenor::STANDARD);
+
+    println!("Fetching {} series from {}...\n", tenors.len(), options.base_url);
+    let mut failures = 0;
+    for tenor in tenors {
+        let key = SeriesKey::euribor(options.frequency, *tenor);
+        match fetch_series(&options.base_url, &key, &output_dir, &options.load) {
+            Ok(result) => {
+                let status = match result.status {
+                    FetchStatus::New => "new".to_string(),
+                    FetchStatus::Unchanged => "unchanged".to_string(),
+                    FetchStatus::Changed { added, revised, removed } =>
+                        format!("changed: {} added, {} revised, {} removed", added, revised, removed),
+                };
+                let last_date = result.last_date.map(|d| d.to_string()).unwrap_or_default();
+                println!("{}: {} observations up to {} ({})", key, result.observations, last_date, status);
+            }
+            Err(e) => {
+                println!("{}: failed, local file kept: {}", key, e);
+                failures += 1;
+            }
+        }
+    }
+
+    if failures > 0 {
+        return Err(format!("{} of {} series could not be fetched", failures, tenors.len()).into());
+    }
+    Ok(())
 }
 
-fn main() -> Result<(), Box<dyn Error>> {
-    // Get the averaged time period from command line arguments or use default
-    let args: Vec<String> = env::args().collect();
-    let averaged_time_days = if args.len() > 1 {
-        args[1].parse().unwrap_or(360)
+// Load the rates from the input selected on the command line
+fn read_rates(options: &Options) -> Result<AllEuriborRates, Box<dyn Error>> {
+    let all_rates = if let Some(path) = options.store.as_ref().filter(|_| options.command != Command::Import) {
+        read_source(&StoreSource::new(path, options.as_of), options)?
+    } else if let Some(path) = &options.bulk {
+        read_source(&BundesbankBulkSource::new(path), options)?
+    } else if let Some(path) = &options.ecb {
+        read_source(&EcbSource::new(path), options)?
+    } else if !options.emmi.is_empty() {
+        read_source(&EmmiSource::new(options.emmi.clone()), options)?
+    } else if let Some(input_dir) = &options.input_dir {
+        read_input_dir(options, input_dir)?
     } else {
-        360
-    };
-    
-    let file_names = [
-        "BBIG1.D.D0.EUR.MMKT.EURIBOR.W01.BID._Z.csv",
-        "BBIG1.D.D0.EUR.MMKT.EURIBOR.M01.BID._Z.csv",
-        "BBIG1.D.D0.EUR.MMKT.EURIBOR.M03.BID._Z.csv",
-        "BBIG1.D.D0.EUR.MMKT.EURIBOR.M06.BID._Z.csv",
-        "BBIG1.D.D0.EUR.MMKT.EURIBOR.M12.BID._Z.csv",
-    ];
-
-    let mut all_rates = AllEuriborRates {
-        w01: Vec::new(),
-        m01: Vec::new(),
-        m03: Vec::new(),
-        m06: Vec::new(),
-        m12: Vec::new(),
+        let tenors = options.tenors.as_deref().unwrap_or(&Tenor::STANDARD);
+        read_source(&BundesbankSource::default_files(Frequency::Daily, tenors), options)?
     };
+    Ok(all_rates)
+}
 
-    println!("Reading CSV files...\n");
-    for (i, file_name) in file_names.iter().enumerate() {
-        println!("Reading {}...", file_name);
-        let rates = read_csv(file_name)
-            .map_err(|e| format!("Failed to read CSV {}: {}", file_name, e))?;
-        
-        println!("Total records: {}", rates.len());
-        println!("First record:");
-        for rate in rates.iter().take(1) {
-            println!(" Date: {}, Rate: {}", rate.date, rate.rate);
-        }
-        println!("Last record:");
-        for rate in rates.iter().rev().take(1) {
-            println!(" Date: {}, Rate: {}", rate.date, rate.rate);
-        }
-        println!();
+// Merge the selected input into the rate store
+fn run_import(options: &Options) -> Result<(), Box<dyn Error>> {
+    let path = options.store.as_ref().ok_or("import requires --store <file>")?;
+    let all_rates = read_rates(options)?;
+
+    println!("Importing into {}...", path.display());
+    let source: Vec<String> = all_rates.sources();
+    let source = if source.is_empty() { "unknown".to_string() } else { source.join("; ") };
+    let summary = RateStore::open(path)?.import(&all_rates, &source)?;
+
+    println!("Import {} at {}:", summary.import_id, summary.loaded_at);
+    for (tenor, counts) in &summary.tenors {
+        println!(" {}: {} added, {} updated, {} removed, {} unchanged",
+            tenor, counts.added, counts.updated, counts.removed, counts.unchanged);
+    }
+    Ok(())
+}
+
+// List the imports kept in the rate store
+fn run_vintages(options: &Options) -> Result<(), Box<dyn Error>> {
+    let path = options.store.as_ref().ok_or("vintages requires --store <file>")?;
+    for import in RateStore::open(path)?.imports()? {
+        println!("{:>4}  {}  {}", import.id, import.loaded_at, import.source);
+    }
+    Ok(())
+}
 
-        if rates.is_empty() {
-            return Err(format!("No valid rates found in the CSV file: {}", file_name).into());
+// List the observations that differ between two vintages of the rate store
+fn run_diff(options: &Options) -> Result<(), Box<dyn Error>> {
+    let path = options.store.as_ref().ok_or("diff requires --store <file>")?;
+    let from = options.diff_from.ok_or("diff requires --from <vintage>")?;
+    let store = RateStore::open(path)?;
+    let mut changes = store.diff(from, options.as_of)?;
+    if let Some(tenors) = &options.tenors {
+        changes.retain(|c| tenors.contains(&c.tenor));
+    }
+
+    println!("Changes from import {} to import {}:", store.resolve(from)?, store.resolve(options.as_of)?);
+    for change in &changes {
+        match change.kind {
+            ChangeKind::Added { rate } => println!(" {} {}: added {}", change.tenor, change.date, rate),
+            ChangeKind::Removed { rate } => println!(" {} {}: removed {}", change.tenor, change.date, rate),
+            ChangeKind::Changed { old, new } => println!(" {} {}: changed {} -> {}", change.tenor, change.date, old, new),
         }
+    }
+    println!("{} observations differ", changes.len());
+    Ok(())
+}
+
+// Check the selected input and fail if any problem is found
+fn run_validate(options: &Options) -> Result<(), Box<dyn Error>> {
+    let all_rates = read_rates(options)?;
+    let issues = validate(&all_rates, &options.validation);
 
-        match i {
-            0 => all_rates.w01 = rates,
-            1 => all_rates.m01 = rates,
-            2 => all_rates.m03 = rates,
-            3 => all_rates.m06 = rates,
-            4 => all_rates.m12 = rates,
-            _ => unreachable!(),
+    println!("Validation:");
+    for issue in &issues {
+        println!(" {}", issue);
+    }
+    for tenor in all_rates.tenors() {
+        let count = issues.iter().filter(|i| i.tenor == tenor).count();
+        println!("{}: {} problems", tenor, count);
+    }
+
+    if !issues.is_empty() {
+        return Err(format!("{} problems found", issues.len()).into());
+    }
+    Ok(())
+}
+
+// List the resets of an anchored loan and the average rate it paid
+fn run_schedule(options: &Options) -> Result<(), Box<dyn Error>> {
+    let anchor = options.anchor.ok_or("schedule requires --anchor <date|month-day|day>")?;
+    let all_rates = read_rates(options)?;
+    let data_start = all_rates.start_date().ok_or("No rates loaded")?;
+    let data_end = all_rates.end_date().ok_or("No rates loaded")?;
+    let from = options.window_start
+        .or(match anchor {
+            Anchor::Date(date) => Some(date),
+            Anchor::DayOfMonth(_) | Anchor::MonthDay(..) => None,
+        })
+        .unwrap_or(data_start);
+    let to = options.window_end.unwrap_or(data_end);
+
+    for (tenor, rates) in all_rates.iter() {
+        let schedule = reset_schedule(rates, tenor, anchor, from, to, &options.average)?;
+        println!("{} loan anchored at {}, {} to {}:", tenor, anchor, schedule.window_start, schedule.window_end);
+        println!(" {:<10}  {:<10}  {:<10}  {:>8}  {:>10}", "Reset", "Fixing", "Until", "Rate", "Interest");
+        for period in &schedule.periods {
+            let (fixing_date, rate) = match period.fixing {
+                Some((date, rate)) => (date.to_string(), format!("{:.3}", rate)),
+                None => ("-".to_string(), "-".to_string()),
+            };
+            println!(" {:<10}  {:<10}  {:<10}  {:>8}  {:>10.4}",
+                period.reset_date, fixing_date, period.end_date, rate, period.interest);
+        }
+        match schedule.average {
+            Some(average) => println!("Average rate paid: {:.4} % ({}, {}{})\n",
+                average, options.average.method, options.average.day_count,
+                if options.average.per_actual_year { ", per 365 days" } else { "" }),
+            None => println!("No fixing available in the window\n"),
         }
     }
+    Ok(())
+}
 
-    println!("Calculating average rates for the forward period of {} days...", averaged_time_days);
-    let (averages, averaged_time_mark) = calculate_average_rates(&all_rates, averaged_time_days);
+// Calculate the averages and write the chart page
+fn run_chart(options: &Options) -> Result<(), Box<dyn Error>> {
+    let averaged_time_days = &options.averaged_time_days;
+    let all_rates = read_rates(options)?;
+
+    let days: Vec<String> = averaged_time_days.iter().map(|d| d.to_string()).collect();
+    println!("Calculating average rates for the {} period of {} days...", options.average.direction, days.join(", "));
+    let mut averages = calculate_average_rates(&all_rates, averaged_time_days, &options.average)?;
+    for path in &options.scenarios {
+        let scenario = Scenario::read(path)?;
+        println!("Calculating average rates for scenario {}...", scenario.name);
+        averages.extend(scenario_average_rates(&all_rates, &scenario, averaged_time_days, &options.average)?);
+    }
     
     println!("Creating chart data...");
-    let chart_data = create_chart_data(&all_rates, &averages, averaged_time_mark, averaged_time_days)?;
+    let chart_data = create_chart_data(&all_rates, &averages, &options.chart)?;
     
     println!("Generating HTML content...");
-    let html_content = generate_html(&chart_data, averaged_time_days);
+    let mut page_info = PageInfo::new(&all_rates, &averages);
+    if options.chart.business_days_axis {
+        page_info.hide_closing_days(&all_rates);
+    }
+    let html_content = generate_html(&chart_data, &page_info);
     
     println!("Writing HTML file...");
     let mut file = File::create("euribor_cost_chart.html")?;
@@ -327,3 +597,24 @@ fn main() -> Result<(), Box<dyn Error>> {
     println!("Chart created successfully: euribor_cost_chart.html");
     Ok(())
 }
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let mut options = parse_args()?;
+    match options.command {
+        Command::Chart => run_chart(&options),
+        Command::Fetch => run_fetch(&options),
+        Command::Import => {
+            // The store keeps observed values only
+            options.load.missing_value_policy = MissingValuePolicy::Drop;
+            run_import(&options)
+        }
+        Command::Vintages => run_vintages(&options),
+        Command::Diff => run_diff(&options),
+        Command::Schedule => run_schedule(&options),
+        Command::Validate => {
+            // Gaps must stay visible to be reported
+            options.load.missing_value_policy = MissingValuePolicy::Drop;
+            run_validate(&options)
+        }
+    }
+}
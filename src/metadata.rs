@@ -0,0 +1,60 @@
+//! Metadata carried in the header lines of a Bundesbank CSV export.
+
+use chrono::NaiveDateTime;
+use csv::StringRecord;
+
+/// Descriptive header of a loaded series.
+#[derive(Debug, Clone, Default)]
+pub struct SeriesMetadata {
+    /// Series key from the column header, e.g. `BBIG1.D.D0.EUR.MMKT.EURIBOR.M03.BID._Z`.
+    pub series_key: Option<String>,
+    /// Series title, e.g. "Money market rates / EURIBOR / Three-month funds / Daily data".
+    pub title: Option<String>,
+    /// Unit of the values, e.g. "% p.a.".
+    pub unit: Option<String>,
+    /// Unit multiplier, e.g. "one".
+    pub unit_multiplier: Option<String>,
+    /// Time of the last update of the series by the publisher.
+    pub last_update: Option<NaiveDateTime>,
+    /// Source of the data.
+    pub source: Option<String>,
+    /// Comments and any header lines not recognised above, as "label: value".
+    pub notes: Vec<String>,
+    /// Number of header lines preceding the first observation.
+    pub header_lines: usize,
+}
+
+// Parse the "last update" field, e.g. "2024-05-02 08:47:05" or "02.05.2024 08:47:05 Uhr"
+fn parse_timestamp(value: &str) -> Option<NaiveDateTime> {
+    let value = value.trim_end_matches("Uhr").trim();
+    ["%Y-%m-%d %H:%M:%S", "%Y-%m-%d %H:%M", "%d.%m.%Y %H:%M:%S", "%d.%m.%Y %H:%M"].iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(value, format).ok())
+}
+
+impl SeriesMetadata {
+    /// Record one header line of the CSV file for the series whose values are in `column`.
+    pub(crate) fn apply_header_record(&mut self, record: &StringRecord, column: usize) {
+        self.header_lines += 1;
+
+        let label = record.get(0).unwrap_or("").trim();
+        let value = record.get(column).unwrap_or("").trim();
+        if value.is_empty() {
+            return;
+        }
+
+        match label.to_lowercase().as_str() {
+            // The first line holds the series key, the second the title
+            "" if self.header_lines == 1 => self.series_key = Some(value.to_string()),
+            "" if self.title.is_none() => self.title = Some(value.to_string()),
+            "unit" | "einheit" => self.unit = Some(value.to_string()),
+            "unit multiplier" | "einheitenmultiplikator" | "dimension" => self.unit_multiplier = Some(value.to_string()),
+            "last update" | "stand vom" | "letzte aktualisierung" => match parse_timestamp(value) {
+                Some(timestamp) => self.last_update = Some(timestamp),
+                None => self.notes.push(format!("{}: {}", label, value)),
+            },
+            "source" | "quelle" => self.source = Some(value.to_string()),
+            "" => self.notes.push(value.to_string()),
+            _ => self.notes.push(format!("{}: {}", label, value)),
+        }
+    }
+}
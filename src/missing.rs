@@ -0,0 +1,169 @@
+//! Handling of missing and unparsable values in loaded series.
+
+use crate::error::{Error, Result};
+use crate::rates::EuriborRate;
+use chrono::NaiveDate;
+use std::fmt;
+use std::str::FromStr;
+
+/// What to do with observations that carry no usable value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MissingValuePolicy {
+    /// Leave the dates out of the series.
+    Drop,
+    /// Repeat the last valid value.
+    #[default]
+    ForwardFill,
+    /// Interpolate linearly between the surrounding valid values by calendar day.
+    Interpolate,
+    /// Fail the load.
+    Error,
+}
+
+impl fmt::Display for MissingValuePolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            MissingValuePolicy::Drop => "drop",
+            MissingValuePolicy::ForwardFill => "forward-fill",
+            MissingValuePolicy::Interpolate => "interpolate",
+            MissingValuePolicy::Error => "error",
+        })
+    }
+}
+
+impl FromStr for MissingValuePolicy {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "drop" => Ok(MissingValuePolicy::Drop),
+            "forward-fill" | "ffill" => Ok(MissingValuePolicy::ForwardFill),
+            "interpolate" | "linear" => Ok(MissingValuePolicy::Interpolate),
+            "error" => Ok(MissingValuePolicy::Error),
+            _ => Err(Error::InvalidOption(format!("unknown missing-value policy '{}'", s))),
+        }
+    }
+}
+
+/// Value field of an observation as read from the source file.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RawValue {
+    Value(f64),
+    /// An explicit "no value" marker such as ".", an empty field, "No value available"
+    /// or "Kein Wert vorhanden".
+    NoValue,
+    /// A field that is neither a number nor a known "no value" marker.
+    Unparsable(String),
+}
+
+impl RawValue {
+    /// Classify a value field; `decimal_comma` reads "3,25" as 3.25.
+    pub fn parse(field: &str, decimal_comma: bool) -> RawValue {
+        let field = field.trim();
+        let lower = field.to_lowercase();
+        if field == "." || field.is_empty() || lower.contains("no value") || lower.contains("kein wert") {
+            return RawValue::NoValue;
+        }
+
+        let number = if decimal_comma { field.replace(',', ".") } else { field.to_string() };
+        match number.parse::<f64>() {
+            Ok(value) if value.is_finite() => RawValue::Value(value),
+            _ => RawValue::Unparsable(field.to_string()),
+        }
+    }
+}
+
+/// A single observation before the missing-value policy is applied.
+#[derive(Debug, Clone)]
+pub struct RawObservation {
+    pub date: NaiveDate,
+    pub value: RawValue,
+    pub flag: Option<String>,
+}
+
+/// A run of consecutive observations without a usable value.
+#[derive(Debug, Clone)]
+pub struct Gap {
+    /// Date of the first missing observation.
+    pub start: NaiveDate,
+    /// Date of the last missing observation.
+    pub end: NaiveDate,
+    /// Number of missing observations in the run.
+    pub missing: usize,
+    /// Dates and raw text of the observations whose value could not be parsed.
+    pub unparsable: Vec<(NaiveDate, String)>,
+    /// Whether synthetic values were inserted for the run.
+    pub filled: bool,
+}
+
+/// Apply `policy` to the raw observations of one series.
+///
+/// Returns the resulting fixings and one [`Gap`] per run of missing values.
+/// Leading gaps, and trailing gaps under interpolation, cannot be filled and
+/// are dropped under every policy except [`MissingValuePolicy::Error`].
+pub fn resolve_missing(raw: Vec<RawObservation>, policy: MissingValuePolicy) -> Result<(Vec<EuriborRate>, Vec<Gap>)> {
+    let mut rates = Vec::with_capacity(raw.len());
+    let mut gaps = Vec::new();
+    let mut pending: Vec<RawObservation> = Vec::new();
+
+    for observation in raw {
+        match observation.value {
+            RawValue::Value(rate) => {
+                if !pending.is_empty() {
+                    let next = Some((observation.date, rate));
+                    gaps.push(close_gap(&mut rates, std::mem::take(&mut pending), next, policy)?);
+                }
+                rates.push(EuriborRate { date: observation.date, rate, flag: observation.flag });
+            }
+            _ => pending.push(observation),
+        }
+    }
+    if !pending.is_empty() {
+        gaps.push(close_gap(&mut rates, pending, None, policy)?);
+    }
+
+    Ok((rates, gaps))
+}
+
+// Fill (or not) one run of missing observations and describe it
+fn close_gap(rates: &mut Vec<EuriborRate>, run: Vec<RawObservation>, next: Option<(NaiveDate, f64)>, policy: MissingValuePolicy) -> Result<Gap> {
+    let unparsable: Vec<(NaiveDate, String)> = run.iter()
+        .filter_map(|o| match &o.value {
+            RawValue::Unparsable(text) => Some((o.date, text.clone())),
+            _ => None,
+        })
+        .collect();
+
+    if policy == MissingValuePolicy::Error {
+        let first = &run[0];
+        let value = match &first.value {
+            RawValue::Unparsable(text) => text.clone(),
+            _ => first.flag.clone().unwrap_or_else(|| "no value".to_string()),
+        };
+        return Err(Error::MissingValue { date: first.date, value });
+    }
+
+    let previous = rates.last().map(|r| (r.date, r.rate));
+    let mut gap = Gap {
+        start: run[0].date,
+        end: run[run.len() - 1].date,
+        missing: run.len(),
+        unparsable,
+        filled: false,
+    };
+
+    let fill: Box<dyn Fn(NaiveDate) -> f64> = match (policy, previous, next) {
+        (MissingValuePolicy::ForwardFill, Some((_, last)), _) => Box::new(move |_| last),
+        (MissingValuePolicy::Interpolate, Some((d0, r0)), Some((d1, r1))) => {
+            let span = (d1 - d0).num_days() as f64;
+            Box::new(move |date| r0 + (r1 - r0) * (date - d0).num_days() as f64 / span)
+        }
+        _ => return Ok(gap),
+    };
+
+    for observation in run {
+        rates.push(EuriborRate { date: observation.date, rate: fill(observation.date), flag: observation.flag });
+    }
+    gap.filled = true;
+    Ok(gap)
+}
@@ -0,0 +1,226 @@
+//! Projected fixings beyond the end of the loaded data.
+
+use crate::average::MAX_STALE_BUSINESS_DAYS;
+use crate::calendar::{add_business_days, is_business_day};
+use crate::error::{Error, Result};
+use crate::rates::{AllEuriborRates, EuriborRate, Series};
+use crate::tenor::Tenor;
+use chrono::{Duration, NaiveDate};
+use std::fmt;
+use std::str::FromStr;
+
+/// Flag attached to projected fixings.
+pub const PROJECTED_FLAG: &str = "Projected";
+
+/// How future fixings are projected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Projection {
+    /// Every tenor stays at its last fixing.
+    #[default]
+    Flat,
+    /// Forward rates implied by the last fixings of all tenors.
+    ForwardCurve,
+}
+
+impl Projection {
+    /// Short description used in chart legends.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Projection::Flat => "last fixing",
+            Projection::ForwardCurve => "implied forwards",
+        }
+    }
+}
+
+impl fmt::Display for Projection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Projection::Flat => "flat",
+            Projection::ForwardCurve => "forward-curve",
+        })
+    }
+}
+
+impl FromStr for Projection {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "flat" => Ok(Projection::Flat),
+            "forward-curve" | "forwards" | "forward" => Ok(Projection::ForwardCurve),
+            _ => Err(Error::InvalidOption(format!("unknown projection '{}'", s))),
+        }
+    }
+}
+
+/// Extend every current series with projected fixings on each TARGET business day up to `until`.
+///
+/// Series whose last fixing lies more than a few business days before the
+/// end of the data have been discontinued and are left as they are.
+pub fn project(all_rates: &AllEuriborRates, projection: Projection, until: NaiveDate) -> Result<AllEuriborRates> {
+    match projection {
+        Projection::Flat => Ok(flat(all_rates, until)),
+        Projection::ForwardCurve => forward_curve(all_rates, until),
+    }
+}
+
+// Append the projected fixings returned by `rate` for each business day after
+// the last fixing of every series that is still current at the end of the data
+pub(crate) fn extend<F>(all_rates: &AllEuriborRates, until: NaiveDate, rate: F) -> AllEuriborRates
+where
+    F: Fn(Tenor, &EuriborRate, NaiveDate) -> f64,
+{
+    let end_date = all_rates.end_date();
+    let mut projected = AllEuriborRates::new();
+    for (tenor, series) in all_rates.iter_series() {
+        let mut series: Series = series.clone();
+        let current = series.rates.last()
+            .filter(|last| end_date.is_some_and(|end| add_business_days(last.date, MAX_STALE_BUSINESS_DAYS) >= end))
+            .cloned();
+        if let Some(last) = current {
+            let dates = (last.date + Duration::days(1)).iter_days().take_while(|d| *d <= until);
+            series.rates.extend(dates.filter(|d| is_business_day(*d)).map(|date| EuriborRate {
+                date,
+                rate: rate(tenor, &last, date),
+                flag: Some(PROJECTED_FLAG.to_string()),
+            }));
+        }
+        projected.insert(tenor, series);
+    }
+    projected
+}
+
+/// Extend every current series with its last fixing on each TARGET business day up to `until`.
+pub fn flat(all_rates: &AllEuriborRates, until: NaiveDate) -> AllEuriborRates {
+    extend(all_rates, until, |_, last, _| last.rate)
+}
+
+/// Discount factors bootstrapped from Euribor deposit rates fixed on one day.
+#[derive(Debug, Clone)]
+pub struct DiscountCurve {
+    spot: NaiveDate,
+    // Days from spot and log discount factor of each node, starting at (0, 0)
+    nodes: Vec<(f64, f64)>,
+}
+
+impl DiscountCurve {
+    /// Bootstrap the curve from simple ACT/360 deposit rates (in percent) of the given tenors.
+    ///
+    /// Each deposit from `spot` to its maturity fixes one discount factor;
+    /// in between, log discount factors are interpolated linearly (flat
+    /// forwards), and beyond the longest tenor its zero rate is held.
+    pub fn bootstrap(spot: NaiveDate, deposits: &[(Tenor, f64)]) -> Result<Self> {
+        let mut nodes = vec![(0.0, 0.0)];
+        let mut deposits = deposits.to_vec();
+        deposits.sort_by_key(|(tenor, _)| *tenor);
+        for (tenor, rate) in deposits {
+            let days = (tenor.advance(spot, 1) - spot).num_days() as f64;
+            let discount = 1.0 / (1.0 + rate / 100.0 * days / 360.0);
+            if discount <= 0.0 {
+                return Err(Error::InvalidOption(format!("cannot bootstrap the {} rate {}", tenor, rate)));
+            }
+            nodes.push((days, discount.ln()));
+        }
+        if nodes.len() < 2 {
+            return Err(Error::NoData("no deposit rates to bootstrap a curve from".to_string()));
+        }
+        Ok(DiscountCurve { spot, nodes })
+    }
+
+    /// Discount factor from the spot date to `date`.
+    pub fn discount(&self, date: NaiveDate) -> f64 {
+        let t = (date - self.spot).num_days() as f64;
+        let i = self.nodes.partition_point(|&(days, _)| days < t);
+        let log_discount = match self.nodes.get(i) {
+            Some(&(days, log_df)) if days == t => log_df,
+            Some(&(t1, y1)) if i > 0 => {
+                let (t0, y0) = self.nodes[i - 1];
+                y0 + (y1 - y0) * (t - t0) / (t1 - t0)
+            }
+            Some(_) => 0.0,
+            None => {
+                let &(days, log_df) = self.nodes.last().expect("curve has nodes");
+                log_df * t / days
+            }
+        };
+        log_discount.exp()
+    }
+
+    /// Simple ACT/360 forward rate in percent for a `tenor` deposit starting on `date`.
+    pub fn forward_rate(&self, tenor: Tenor, date: NaiveDate) -> f64 {
+        let maturity = tenor.advance(date, 1);
+        let years = (maturity - date).num_days() as f64 / 360.0;
+        (self.discount(date) / self.discount(maturity) - 1.0) / years * 100.0
+    }
+}
+
+/// Extend every current series with the forward rates implied by the latest fixings.
+///
+/// The curve is bootstrapped from the last fixing of every tenor on the
+/// last loaded date; a tenor without a fixing on that date is left out of
+/// the curve but still projected from it.
+pub fn forward_curve(all_rates: &AllEuriborRates, until: NaiveDate) -> Result<AllEuriborRates> {
+    let spot = all_rates.end_date().ok_or_else(|| Error::NoData("no rates loaded".to_string()))?;
+    let deposits: Vec<(Tenor, f64)> = all_rates.iter()
+        .filter_map(|(tenor, rates)| rates.last().filter(|r| r.date == spot).map(|r| (tenor, r.rate)))
+        .collect();
+    let curve = DiscountCurve::bootstrap(spot, &deposits)?;
+    Ok(extend(all_rates, until, |tenor, _, date| curve.forward_rate(tenor, date)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn forwards_from_spot_reproduce_the_deposits() {
+        let spot = date(2024, 1, 15);
+        let deposits = [(Tenor::weeks(1), 3.9), (Tenor::months(1), 3.87), (Tenor::months(3), 3.93), (Tenor::months(6), 3.89), (Tenor::months(12), 3.6)];
+        let curve = DiscountCurve::bootstrap(spot, &deposits).unwrap();
+        for (tenor, rate) in deposits {
+            assert!((curve.forward_rate(tenor, spot) - rate).abs() < 1e-12, "{}", tenor);
+        }
+        assert!(DiscountCurve::bootstrap(spot, &[]).is_err());
+    }
+
+    #[test]
+    fn flat_curve_gives_flat_forwards() {
+        // Deposits with the same continuously compounded zero rate of 3% (ACT/360)
+        let spot = date(2024, 1, 15);
+        let zero = 0.03;
+        let deposit = |days: f64| ((zero * days / 360.0).exp() - 1.0) * 360.0 / days * 100.0;
+        let deposits: Vec<(Tenor, f64)> = [Tenor::months(1), Tenor::months(3), Tenor::months(12)]
+            .into_iter()
+            .map(|tenor| (tenor, deposit((tenor.advance(spot, 1) - spot).num_days() as f64)))
+            .collect();
+        let curve = DiscountCurve::bootstrap(spot, &deposits).unwrap();
+
+        // Within the curve and beyond its last node
+        for start in [spot, date(2024, 2, 1), date(2024, 7, 31), date(2025, 3, 3)] {
+            assert!((curve.forward_rate(Tenor::weeks(1), start) - deposit(7.0)).abs() < 1e-9, "{}", start);
+            let days = (Tenor::months(3).advance(start, 1) - start).num_days() as f64;
+            assert!((curve.forward_rate(Tenor::months(3), start) - deposit(days)).abs() < 1e-9, "{}", start);
+        }
+    }
+
+    #[test]
+    fn forwards_between_nodes_interpolate_log_discount_factors() {
+        // Nodes after 31 days (15 Feb) and 91 days (15 Apr)
+        let spot = date(2024, 1, 15);
+        let curve = DiscountCurve::bootstrap(spot, &[(Tenor::months(1), 3.8), (Tenor::months(3), 4.0)]).unwrap();
+        let y0 = -(1.0 + 0.038 * 31.0 / 360.0_f64).ln();
+        let y1 = -(1.0 + 0.040 * 91.0 / 360.0_f64).ln();
+
+        // 1 Mar is 46 days from spot, a quarter of the way from the first node to the second
+        let interpolated = y0 + (y1 - y0) * 15.0 / 60.0;
+        assert!((curve.discount(date(2024, 3, 1)) - interpolated.exp()).abs() < 1e-15);
+
+        // A week inside the segment discounts at its constant slope
+        let expected = ((-(y1 - y0) * 7.0 / 60.0).exp() - 1.0) * 360.0 / 7.0 * 100.0;
+        assert!((curve.forward_rate(Tenor::weeks(1), date(2024, 3, 1)) - expected).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,151 @@
+//! Data model for daily Euribor fixings.
+
+use crate::metadata::SeriesMetadata;
+use crate::missing::Gap;
+use crate::tenor::Tenor;
+use chrono::{NaiveDate, NaiveDateTime};
+use std::collections::BTreeMap;
+
+/// A single Euribor fixing.
+#[derive(Debug, Clone)]
+pub struct EuriborRate {
+    pub date: NaiveDate,
+    /// Rate in percent per annum.
+    pub rate: f64,
+    /// Observation flag or comment exported next to the value, e.g. "Provisional value".
+    pub flag: Option<String>,
+}
+
+impl EuriborRate {
+    /// Whether the publisher attached a flag or comment to this observation.
+    pub fn is_flagged(&self) -> bool {
+        self.flag.is_some()
+    }
+
+    /// Whether the observation is marked as provisional.
+    pub fn is_provisional(&self) -> bool {
+        self.flag.as_deref().is_some_and(|f| {
+            let f = f.to_lowercase();
+            f.contains("provisional") || f.contains("vorläufig")
+        })
+    }
+}
+
+/// Fixings of one tenor together with the metadata of the file they came from.
+#[derive(Debug, Clone, Default)]
+pub struct Series {
+    pub metadata: SeriesMetadata,
+    pub rates: Vec<EuriborRate>,
+    /// Runs of missing values and how they were handled.
+    pub gaps: Vec<Gap>,
+}
+
+impl Series {
+    /// Number of observations carrying a flag or comment.
+    pub fn flagged_count(&self) -> usize {
+        self.rates.iter().filter(|r| r.is_flagged()).count()
+    }
+
+    /// Number of observations marked as provisional.
+    pub fn provisional_count(&self) -> usize {
+        self.rates.iter().filter(|r| r.is_provisional()).count()
+    }
+
+    /// Number of observations that were filled in rather than observed.
+    pub fn filled_count(&self) -> usize {
+        self.gaps.iter().filter(|g| g.filled).map(|g| g.missing).sum()
+    }
+}
+
+/// Daily fixings keyed by tenor, iterated from the shortest to the longest tenor.
+#[derive(Debug, Default)]
+pub struct AllEuriborRates {
+    series: BTreeMap<Tenor, Series>,
+}
+
+impl AllEuriborRates {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the series of a tenor, replacing any previously loaded series.
+    pub fn insert(&mut self, tenor: Tenor, series: Series) {
+        self.series.insert(tenor, series);
+    }
+
+    /// Keep only the tenors for which `keep` returns true.
+    pub fn retain<F: FnMut(Tenor) -> bool>(&mut self, mut keep: F) {
+        self.series.retain(|tenor, _| keep(*tenor));
+    }
+
+    /// Fixings of a tenor.
+    pub fn get(&self, tenor: Tenor) -> Option<&[EuriborRate]> {
+        self.series.get(&tenor).map(|s| s.rates.as_slice())
+    }
+
+    /// Series of a tenor including its metadata.
+    pub fn series(&self, tenor: Tenor) -> Option<&Series> {
+        self.series.get(&tenor)
+    }
+
+    /// Loaded tenors in ascending order.
+    pub fn tenors(&self) -> impl Iterator<Item = Tenor> + '_ {
+        self.series.keys().copied()
+    }
+
+    /// Fixings of the loaded tenors in ascending tenor order.
+    pub fn iter(&self) -> impl Iterator<Item = (Tenor, &[EuriborRate])> {
+        self.series.iter().map(|(tenor, series)| (*tenor, series.rates.as_slice()))
+    }
+
+    /// Take the loaded series out of the collection.
+    pub fn into_series(self) -> impl Iterator<Item = (Tenor, Series)> {
+        self.series.into_iter()
+    }
+
+    /// Loaded series including their metadata in ascending tenor order.
+    pub fn iter_series(&self) -> impl Iterator<Item = (Tenor, &Series)> {
+        self.series.iter().map(|(tenor, series)| (*tenor, series))
+    }
+
+    /// Most recent publisher update time over all series.
+    pub fn last_update(&self) -> Option<NaiveDateTime> {
+        self.series.values()
+            .filter_map(|s| s.metadata.last_update)
+            .max()
+    }
+
+    /// Distinct data sources named in the series metadata.
+    pub fn sources(&self) -> Vec<String> {
+        let mut sources: Vec<String> = self.series.values()
+            .filter_map(|s| s.metadata.source.clone())
+            .collect();
+        sources.sort();
+        sources.dedup();
+        sources
+    }
+
+    pub fn len(&self) -> usize {
+        self.series.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.series.is_empty()
+    }
+
+    /// Earliest date found in any series.
+    pub fn start_date(&self) -> Option<NaiveDate> {
+        self.series.values()
+            .filter_map(|s| s.rates.first())
+            .map(|r| r.date)
+            .min()
+    }
+
+    /// Latest date found in any series.
+    pub fn end_date(&self) -> Option<NaiveDate> {
+        self.series.values()
+            .filter_map(|s| s.rates.last())
+            .map(|r| r.date)
+            .max()
+    }
+}
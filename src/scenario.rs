@@ -0,0 +1,144 @@
+//! User-supplied paths of future rates appended after the loaded data.
+
+use crate::error::{Error, Result};
+use crate::loader::{decode_text, parse_date};
+use crate::projection::extend;
+use crate::rates::AllEuriborRates;
+use crate::tenor::Tenor;
+use chrono::NaiveDate;
+use csv::ReaderBuilder;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+/// What the values of a scenario file describe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScenarioKind {
+    /// Fixings in percent.
+    Rates,
+    /// Parallel shifts of the last fixings in basis points.
+    ShiftBp,
+}
+
+/// One value of a scenario, for a single tenor or all of them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScenarioPoint {
+    pub date: NaiveDate,
+    /// Tenor the value applies to; `None` applies it to every tenor.
+    pub tenor: Option<Tenor>,
+    pub value: f64,
+}
+
+/// A named path of future rates.
+///
+/// Between its points the rates move linearly, starting from the last
+/// fixing of each tenor (or from no shift), and the last point is held.
+#[derive(Debug, Clone)]
+pub struct Scenario {
+    /// Name shown in chart legends, by default the file name without extension.
+    pub name: String,
+    pub kind: ScenarioKind,
+    pub points: Vec<ScenarioPoint>,
+}
+
+// Find a column by any of its accepted header names
+fn find_column(header: &csv::StringRecord, names: &[&str]) -> Option<usize> {
+    header.iter().position(|cell| names.contains(&cell.trim().to_lowercase().as_str()))
+}
+
+impl Scenario {
+    /// Read a scenario file, named after the file.
+    pub fn read<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let name = path.file_stem().map_or_else(|| path.display().to_string(), |s| s.to_string_lossy().into_owned());
+        let data = fs::read(path).map_err(|e| Error::in_file(path, e.into()))?;
+        Scenario::parse(&name, &data).map_err(|e| Error::in_file(path, e))
+    }
+
+    /// Parse the contents of a scenario file.
+    ///
+    /// The file is a CSV with a header line naming a `date` column, an
+    /// optional `tenor` column (empty, `all` or `*` for every tenor) and
+    /// either a `rate` column in percent or a `shift_bp` column in basis
+    /// points. Lines starting with `#` are ignored. Points given for a tenor
+    /// replace the points given for all tenors.
+    pub fn parse(name: &str, data: &[u8]) -> Result<Self> {
+        let text = decode_text(data);
+        let mut reader = ReaderBuilder::new()
+            .flexible(true)
+            .comment(Some(b'#'))
+            .trim(csv::Trim::All)
+            .from_reader(text.as_bytes());
+
+        let header = reader.headers()?.clone();
+        let date_column = find_column(&header, &["date"])
+            .ok_or_else(|| Error::Format("scenario has no date column".to_string()))?;
+        let tenor_column = find_column(&header, &["tenor"]);
+        let (kind, value_column) = match (find_column(&header, &["rate"]), find_column(&header, &["shift_bp", "shift"])) {
+            (Some(column), None) => (ScenarioKind::Rates, column),
+            (None, Some(column)) => (ScenarioKind::ShiftBp, column),
+            _ => return Err(Error::Format("scenario needs either a rate or a shift_bp column".to_string())),
+        };
+
+        let mut points = Vec::new();
+        for result in reader.records() {
+            let record = result?;
+            let date = parse_date(record.get(date_column).unwrap_or(""))?;
+            let tenor = match tenor_column.and_then(|i| record.get(i)).unwrap_or("") {
+                "" | "*" => None,
+                all if all.eq_ignore_ascii_case("all") => None,
+                tenor => Some(tenor.parse()?),
+            };
+            let value = record.get(value_column).unwrap_or("");
+            let value = value.parse()
+                .map_err(|_| Error::Format(format!("invalid scenario value '{}' on {}", value, date)))?;
+            points.push(ScenarioPoint { date, tenor, value });
+        }
+
+        if points.is_empty() {
+            return Err(Error::NoData(format!("scenario '{}' has no points", name)));
+        }
+        points.sort_by_key(|p| p.date);
+        Ok(Scenario { name: name.to_string(), kind, points })
+    }
+
+    // Dates and values the scenario passes through for one tenor
+    fn path(&self, tenor: Tenor) -> Vec<(NaiveDate, f64)> {
+        let own = self.points.iter().any(|p| p.tenor == Some(tenor));
+        self.points.iter()
+            .filter(|p| p.tenor == if own { Some(tenor) } else { None })
+            .map(|p| (p.date, p.value))
+            .collect()
+    }
+
+    /// Extend every series with the scenario fixings on each TARGET business day up to `until`.
+    ///
+    /// Points on or before the last fixing of a tenor are ignored; a tenor
+    /// without points stays at its last fixing. Discontinued tenors are not
+    /// extended.
+    pub fn apply(&self, all_rates: &AllEuriborRates, until: NaiveDate) -> AllEuriborRates {
+        let paths: BTreeMap<Tenor, Vec<(NaiveDate, f64)>> = all_rates.tenors().map(|t| (t, self.path(t))).collect();
+        extend(all_rates, until, |tenor, last, date| {
+            let start = match self.kind {
+                ScenarioKind::Rates => last.rate,
+                ScenarioKind::ShiftBp => 0.0,
+            };
+            let mut previous = (last.date, start);
+            let mut value = start;
+            for &(point_date, point_value) in paths[&tenor].iter().filter(|(d, _)| *d > last.date) {
+                if date <= point_date {
+                    let span = (point_date - previous.0).num_days() as f64;
+                    let elapsed = (date - previous.0).num_days() as f64;
+                    value = previous.1 + (point_value - previous.1) * elapsed / span;
+                    break;
+                }
+                previous = (point_date, point_value);
+                value = point_value;
+            }
+            match self.kind {
+                ScenarioKind::Rates => value,
+                ScenarioKind::ShiftBp => last.rate + value / 100.0,
+            }
+        })
+    }
+}
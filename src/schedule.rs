@@ -0,0 +1,239 @@
+//! Reset schedule of a single loan anchored to a start date.
+
+use crate::average::{Accrual, AverageOptions, Fixings};
+use crate::error::{Error, Result};
+use crate::rates::EuriborRate;
+use crate::tenor::{Tenor, TenorUnit};
+use chrono::{Datelike, Duration, NaiveDate};
+use std::fmt;
+use std::str::FromStr;
+
+/// Where the resets of a loan fall.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Anchor {
+    /// The loan starts on this date and resets every tenor after it.
+    Date(NaiveDate),
+    /// The loan resets on this day of the month (clamped to the end of
+    /// shorter months).
+    DayOfMonth(u32),
+    /// The loan resets on this month and day every year, and every tenor in
+    /// between, e.g. a 12m loan resetting every 15 March.
+    MonthDay(u32, u32),
+}
+
+// The given day of a month, clamped to the end of shorter months
+fn day_in_month(year: i32, month: u32, day: u32) -> NaiveDate {
+    (1..=day).rev()
+        .find_map(|d| NaiveDate::from_ymd_opt(year, month, d))
+        .expect("valid month")
+}
+
+impl Anchor {
+    /// First reset date of the loan for a window starting on `from`: the
+    /// anchor date, or the last anchored reset on or before `from`.
+    pub fn start_date(&self, tenor: Tenor, from: NaiveDate) -> NaiveDate {
+        match *self {
+            Anchor::Date(date) => date,
+            Anchor::DayOfMonth(day) => {
+                let this_month = day_in_month(from.year(), from.month(), day);
+                if this_month <= from {
+                    this_month
+                } else {
+                    let previous = from.with_day(1).expect("first of month") - Duration::days(1);
+                    day_in_month(previous.year(), previous.month(), day)
+                }
+            }
+            Anchor::MonthDay(month, day) => {
+                let this_year = day_in_month(from.year(), month, day);
+                let anniversary = if this_year <= from { this_year } else { day_in_month(from.year() - 1, month, day) };
+                (1..)
+                    .map(|periods| self.reset_date(tenor, anniversary, periods))
+                    .take_while(|reset| *reset <= from)
+                    .last()
+                    .unwrap_or(anniversary)
+            }
+        }
+    }
+
+    /// Reset date `periods` tenors after the first reset `start`.
+    ///
+    /// Monthly resets of a day-of-month anchor stay on the anchored day
+    /// rather than on the day `start` was clamped to in a shorter month.
+    pub fn reset_date(&self, tenor: Tenor, start: NaiveDate, periods: u32) -> NaiveDate {
+        match (*self, tenor.unit()) {
+            (Anchor::DayOfMonth(day) | Anchor::MonthDay(_, day), TenorUnit::Month) => {
+                let month = start.year() * 12 + start.month0() as i32 + (tenor.count() * periods) as i32;
+                day_in_month(month.div_euclid(12), month.rem_euclid(12) as u32 + 1, day)
+            }
+            _ => tenor.advance(start, periods),
+        }
+    }
+}
+
+impl fmt::Display for Anchor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Anchor::Date(date) => write!(f, "{}", date),
+            Anchor::DayOfMonth(day) => write!(f, "day {} of the month", day),
+            Anchor::MonthDay(month, day) => write!(f, "{:02}-{:02} each year", month, day),
+        }
+    }
+}
+
+// Accepts a date ("2020-03-15"), a month and day ("03-15") or a day of the month ("15")
+impl FromStr for Anchor {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+        if let Ok(day) = s.parse::<u32>() {
+            return match day {
+                1..=31 => Ok(Anchor::DayOfMonth(day)),
+                _ => Err(Error::InvalidOption(format!("day of month {} is out of range", day))),
+            };
+        }
+        if let Some((month, day)) = s.split_once('-').filter(|(_, day)| !day.contains('-')) {
+            let (month, day) = (month.parse::<u32>(), day.parse::<u32>());
+            // Checked against a leap year so that 02-29 is accepted
+            return match (month, day) {
+                (Ok(month), Ok(day)) if NaiveDate::from_ymd_opt(2000, month, day).is_some() => Ok(Anchor::MonthDay(month, day)),
+                _ => Err(Error::InvalidOption(format!("invalid month and day '{}'", s))),
+            };
+        }
+        NaiveDate::parse_from_str(s, "%Y-%m-%d")
+            .map(Anchor::Date)
+            .map_err(|e| Error::Date { value: s.to_string(), source: e })
+    }
+}
+
+/// One interest period of a reset schedule.
+#[derive(Debug, Clone)]
+pub struct ResetPeriod {
+    pub reset_date: NaiveDate,
+    /// First day after the period, or after the window or the data if earlier.
+    pub end_date: NaiveDate,
+    /// Date and value of the fixing the period accrues at; `None` before the first fixing.
+    pub fixing: Option<(NaiveDate, f64)>,
+    /// Year fraction of the period inside the window under the day count.
+    pub year_fraction: f64,
+    /// Interest accrued inside the window in percent of the notional.
+    pub interest: f64,
+}
+
+/// Resets of one tenor over a window and the average rate they realized.
+#[derive(Debug, Clone)]
+pub struct ResetSchedule {
+    pub tenor: Tenor,
+    pub window_start: NaiveDate,
+    /// Last day of the window.
+    pub window_end: NaiveDate,
+    pub periods: Vec<ResetPeriod>,
+    /// Average rate paid with the averaging method and rate basis of the
+    /// options, as in [`calculate_average_rates`](crate::calculate_average_rates).
+    pub average: Option<f64>,
+}
+
+/// Build the reset schedule of a `tenor` loan anchored at `anchor` and the
+/// average rate it paid from `from` to `to` inclusive.
+///
+/// Periods that started before the window accrue from `from` at the fixing
+/// of their reset date, so the whole window accrues unless the loan starts
+/// later; the window is cut off after the last fixing.
+pub fn reset_schedule(rates: &[EuriborRate], tenor: Tenor, anchor: Anchor, from: NaiveDate, to: NaiveDate, options: &AverageOptions) -> Result<ResetSchedule> {
+    let last_fixing = rates.iter().map(|r| r.date).max()
+        .ok_or_else(|| Error::NoData(format!("no {} fixings loaded", tenor)))?;
+    let start = anchor.start_date(tenor, from);
+    let window_start = std::cmp::max(from, start);
+    let window_end = std::cmp::min(to, last_fixing);
+    if window_end < window_start {
+        return Err(Error::InvalidOption(format!("empty window from {} to {}", window_start, window_end)));
+    }
+
+    let fixings = Fixings::new(rates);
+    let after_window = window_end + Duration::days(1);
+    let mut periods = Vec::new();
+    let mut resets = 0;
+    let mut reset_date = start;
+
+    while reset_date <= window_end {
+        resets += 1;
+        let next_reset = anchor.reset_date(tenor, start, resets);
+        if next_reset > window_start {
+            let accrual_start = std::cmp::max(reset_date, window_start);
+            let end_date = std::cmp::min(next_reset, after_window);
+            let fixing = fixings.lookup(options.fixing_lookup, reset_date);
+            let year_fraction = options.day_count.year_fraction(accrual_start, end_date);
+            let interest = fixing.map_or(0.0, |(_, rate)| rate * year_fraction);
+            periods.push(ResetPeriod { reset_date, end_date, fixing, year_fraction, interest });
+        }
+        reset_date = next_reset;
+    }
+
+    // Only periods with a fixing count towards the average
+    let mut accrual = Accrual::new(options);
+    for period in &periods {
+        if let Some((_, rate)) = period.fixing {
+            let days = (period.end_date - std::cmp::max(period.reset_date, window_start)).num_days();
+            accrual.add(rate, period.year_fraction, days);
+        }
+    }
+    let average = accrual.rate();
+
+    Ok(ResetSchedule { tenor, window_start, window_end, periods, average })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    // A fixing on every day from 2022 to 2024
+    fn daily_rates() -> Vec<EuriborRate> {
+        date(2022, 1, 1).iter_days()
+            .take_while(|d| d.year() < 2025)
+            .map(|date| EuriborRate { date, rate: 3.0, flag: None })
+            .collect()
+    }
+
+    fn reset_dates(schedule: &ResetSchedule) -> Vec<NaiveDate> {
+        schedule.periods.iter().map(|p| p.reset_date).collect()
+    }
+
+    #[test]
+    fn day_of_month_resets_roll_from_the_anchored_day() {
+        let (from, to) = (date(2023, 2, 1), date(2023, 6, 30));
+        let schedule =
+            reset_schedule(&daily_rates(), Tenor::months(1), Anchor::DayOfMonth(30), from, to, &AverageOptions::default()).unwrap();
+        assert_eq!(reset_dates(&schedule), [
+            date(2023, 1, 30), date(2023, 2, 28), date(2023, 3, 30), date(2023, 4, 30), date(2023, 5, 30), date(2023, 6, 30),
+        ]);
+        assert_eq!(schedule.window_start, from);
+    }
+
+    #[test]
+    fn month_day_anchor_covers_the_whole_window() {
+        let anchor: Anchor = "03-15".parse().unwrap();
+        assert_eq!(anchor, Anchor::MonthDay(3, 15));
+        let (from, to) = (date(2023, 1, 20), date(2024, 6, 30));
+
+        let schedule = reset_schedule(&daily_rates(), Tenor::months(12), anchor, from, to, &AverageOptions::default()).unwrap();
+        assert_eq!(reset_dates(&schedule), [date(2022, 3, 15), date(2023, 3, 15), date(2024, 3, 15)]);
+        assert_eq!(schedule.window_start, from);
+        assert!((schedule.average.unwrap() - 3.0).abs() < 1e-9);
+
+        let schedule = reset_schedule(&daily_rates(), Tenor::months(3), anchor, from, to, &AverageOptions::default()).unwrap();
+        assert_eq!(reset_dates(&schedule)[..3], [date(2022, 12, 15), date(2023, 3, 15), date(2023, 6, 15)]);
+    }
+
+    #[test]
+    fn anchors_parse() {
+        assert_eq!("15".parse::<Anchor>().unwrap(), Anchor::DayOfMonth(15));
+        assert_eq!("02-29".parse::<Anchor>().unwrap(), Anchor::MonthDay(2, 29));
+        assert_eq!("2020-03-15".parse::<Anchor>().unwrap(), Anchor::Date(date(2020, 3, 15)));
+        assert!("02-30".parse::<Anchor>().is_err());
+        assert!("32".parse::<Anchor>().is_err());
+    }
+}
@@ -0,0 +1,158 @@
+//! Bundesbank time-series keys such as `BBIG1.D.D0.EUR.MMKT.EURIBOR.M03.BID._Z`.
+
+use crate::error::Error;
+use crate::tenor::Tenor;
+use std::fmt;
+use std::str::FromStr;
+
+/// Observation frequency of a series.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Frequency {
+    Daily,
+    Monthly,
+    Quarterly,
+    Annual,
+}
+
+impl Frequency {
+    /// Single-letter code used in series keys.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Frequency::Daily => "D",
+            Frequency::Monthly => "M",
+            Frequency::Quarterly => "Q",
+            Frequency::Annual => "A",
+        }
+    }
+}
+
+impl fmt::Display for Frequency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Frequency::Daily => "daily",
+            Frequency::Monthly => "monthly",
+            Frequency::Quarterly => "quarterly",
+            Frequency::Annual => "annual",
+        })
+    }
+}
+
+impl FromStr for Frequency {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_uppercase().as_str() {
+            "D" | "DAILY" => Ok(Frequency::Daily),
+            "M" | "MONTHLY" => Ok(Frequency::Monthly),
+            "Q" | "QUARTERLY" => Ok(Frequency::Quarterly),
+            "A" | "ANNUAL" => Ok(Frequency::Annual),
+            _ => Err(Error::InvalidSeriesKey(format!("unknown frequency '{}'", s))),
+        }
+    }
+}
+
+/// Parsed Bundesbank series key.
+///
+/// The key `BBIG1.D.D0.EUR.MMKT.EURIBOR.M03.BID._Z` reads as dataset `BBIG1`,
+/// daily frequency, reference area `D0`, currency `EUR`, market `MMKT`,
+/// instrument `EURIBOR`, tenor `M03`, price type `BID` and suffix `_Z`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SeriesKey {
+    pub dataset: String,
+    pub frequency: Frequency,
+    pub area: String,
+    pub currency: String,
+    pub market: String,
+    pub instrument: String,
+    pub tenor: Tenor,
+    pub price_type: String,
+    pub suffix: String,
+}
+
+impl SeriesKey {
+    /// Key of the Bundesbank Euribor series for a frequency and tenor.
+    pub fn euribor(frequency: Frequency, tenor: Tenor) -> Self {
+        SeriesKey {
+            dataset: "BBIG1".to_string(),
+            frequency,
+            area: "D0".to_string(),
+            currency: "EUR".to_string(),
+            market: "MMKT".to_string(),
+            instrument: "EURIBOR".to_string(),
+            tenor,
+            price_type: "BID".to_string(),
+            suffix: "_Z".to_string(),
+        }
+    }
+
+    pub fn is_euribor(&self) -> bool {
+        self.instrument.eq_ignore_ascii_case("EURIBOR")
+    }
+
+    /// File name used for the CSV export of this series.
+    pub fn file_name(&self) -> String {
+        format!("{}.csv", self)
+    }
+}
+
+impl fmt::Display for SeriesKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}.{}.{}.{}.{}.{}.{}",
+            self.dataset, self.frequency.code(), self.area, self.currency, self.market,
+            self.instrument, self.tenor.code(), self.price_type, self.suffix)
+    }
+}
+
+impl FromStr for SeriesKey {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<&str> = s.trim().split('.').collect();
+        if parts.len() != 9 || parts.iter().any(|p| p.is_empty()) {
+            return Err(Error::InvalidSeriesKey(s.to_string()));
+        }
+
+        Ok(SeriesKey {
+            dataset: parts[0].to_string(),
+            frequency: parts[1].parse()?,
+            area: parts[2].to_string(),
+            currency: parts[3].to_string(),
+            market: parts[4].to_string(),
+            instrument: parts[5].to_string(),
+            tenor: parts[6].parse()?,
+            price_type: parts[7].to_string(),
+            suffix: parts[8].to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn key_round_trips_through_display() {
+        let text = "BBIG1.D.D0.EUR.MMKT.EURIBOR.M03.BID._Z";
+        let key: SeriesKey = text.parse().unwrap();
+        assert_eq!(key, SeriesKey::euribor(Frequency::Daily, Tenor::months(3)));
+        assert_eq!(key.to_string(), text);
+        assert_eq!(key.file_name(), format!("{}.csv", text));
+
+        let monthly: SeriesKey = "BBIG1.M.D0.EUR.MMKT.EURIBOR.W01.BID._Z".parse().unwrap();
+        assert_eq!((monthly.frequency, monthly.tenor), (Frequency::Monthly, Tenor::weeks(1)));
+        assert_eq!(monthly.to_string().parse::<SeriesKey>().unwrap(), monthly);
+    }
+
+    #[test]
+    fn malformed_keys_are_rejected() {
+        for key in [
+            "BBIG1.D.D0.EUR.MMKT.EURIBOR.M03.BID",
+            "BBIG1.D.D0.EUR.MMKT.EURIBOR.M03.BID._Z.X",
+            "BBIG1.D..EUR.MMKT.EURIBOR.M03.BID._Z",
+            "BBIG1.X.D0.EUR.MMKT.EURIBOR.M03.BID._Z",
+            "BBIG1.D.D0.EUR.MMKT.EURIBOR.Y03.BID._Z",
+        ] {
+            assert!(key.parse::<SeriesKey>().is_err(), "{}", key);
+        }
+    }
+}
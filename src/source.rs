@@ -0,0 +1,78 @@
+//! Common interface of the rate importers.
+
+use crate::error::{Error, Result};
+use crate::loader::{read_bulk_csv, read_csv, LoadOptions};
+use crate::rates::AllEuriborRates;
+use crate::series_key::{Frequency, SeriesKey};
+use crate::tenor::Tenor;
+use std::path::PathBuf;
+
+/// A provider of Euribor fixings.
+///
+/// Every importer produces the same [`AllEuriborRates`] collection, so the
+/// averaging engine and the chart builders work with any of them.
+pub trait RateSource {
+    /// Short description used in console output, e.g. "ECB Data Portal (rates.csv)".
+    fn name(&self) -> String;
+
+    /// Load every series the source provides.
+    fn load(&self, options: &LoadOptions) -> Result<AllEuriborRates>;
+}
+
+/// Bundesbank CSV exports, one file per tenor.
+#[derive(Debug, Clone, Default)]
+pub struct BundesbankSource {
+    pub files: Vec<(Tenor, PathBuf)>,
+}
+
+impl BundesbankSource {
+    pub fn new(files: Vec<(Tenor, PathBuf)>) -> Self {
+        BundesbankSource { files }
+    }
+
+    /// Files named after their series key in the working directory, e.g.
+    /// `BBIG1.D.D0.EUR.MMKT.EURIBOR.M03.BID._Z.csv`.
+    pub fn default_files(frequency: Frequency, tenors: &[Tenor]) -> Self {
+        let files = tenors.iter()
+            .map(|&tenor| (tenor, PathBuf::from(SeriesKey::euribor(frequency, tenor).file_name())))
+            .collect();
+        BundesbankSource { files }
+    }
+}
+
+impl RateSource for BundesbankSource {
+    fn name(&self) -> String {
+        format!("Bundesbank ({} files)", self.files.len())
+    }
+
+    fn load(&self, options: &LoadOptions) -> Result<AllEuriborRates> {
+        let mut all_rates = AllEuriborRates::new();
+        for (tenor, path) in &self.files {
+            let series = read_csv(path, options).map_err(|e| Error::in_file(path, e))?;
+            all_rates.insert(*tenor, series);
+        }
+        Ok(all_rates)
+    }
+}
+
+/// A wide Bundesbank export holding several series in one file.
+#[derive(Debug, Clone)]
+pub struct BundesbankBulkSource {
+    pub path: PathBuf,
+}
+
+impl BundesbankBulkSource {
+    pub fn new<P: Into<PathBuf>>(path: P) -> Self {
+        BundesbankBulkSource { path: path.into() }
+    }
+}
+
+impl RateSource for BundesbankBulkSource {
+    fn name(&self) -> String {
+        format!("Bundesbank bulk export ({})", self.path.display())
+    }
+
+    fn load(&self, options: &LoadOptions) -> Result<AllEuriborRates> {
+        read_bulk_csv(&self.path, options).map_err(|e| Error::in_file(&self.path, e))
+    }
+}
@@ -0,0 +1,531 @@
+//! Local SQLite store of imported observations.
+//!
+//! Every import is kept as a vintage: an observation is stored again only
+//! when an import adds, revises or removes it, so the state of the store as
+//! of any earlier import can be reconstructed.
+
+use crate::error::{Error, Result};
+use crate::loader::LoadOptions;
+use crate::metadata::SeriesMetadata;
+use crate::rates::{AllEuriborRates, EuriborRate, Series};
+use crate::source::RateSource;
+use crate::tenor::Tenor;
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime, Timelike, Utc};
+use rusqlite::{params, Connection, Transaction};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+const SCHEMA_VERSION: i64 = 2;
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS imports (
+        id        INTEGER PRIMARY KEY,
+        source    TEXT NOT NULL,
+        loaded_at TEXT NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS series_versions (
+        tenor           TEXT NOT NULL,
+        import_id       INTEGER NOT NULL REFERENCES imports(id),
+        series_key      TEXT,
+        title           TEXT,
+        unit            TEXT,
+        unit_multiplier TEXT,
+        last_update     TEXT,
+        source          TEXT,
+        PRIMARY KEY (tenor, import_id)
+    );
+    -- A NULL rate records that the import removed the observation
+    CREATE TABLE IF NOT EXISTS observation_versions (
+        tenor     TEXT NOT NULL,
+        date      TEXT NOT NULL,
+        import_id INTEGER NOT NULL REFERENCES imports(id),
+        rate      REAL,
+        flag      TEXT,
+        PRIMARY KEY (tenor, date, import_id)
+    );
+";
+
+// Copy the unversioned tables of schema version 1 into the versioned ones
+const MIGRATE_FROM_V1: &str = "
+    INSERT INTO series_versions (tenor, import_id, series_key, title, unit, unit_multiplier, last_update, source)
+        SELECT tenor, (SELECT COALESCE(MIN(o.import_id), 0) FROM observations o WHERE o.tenor = series.tenor), series_key, title, unit, unit_multiplier, last_update, source
+        FROM series;
+    INSERT INTO observation_versions (tenor, date, import_id, rate, flag)
+        SELECT tenor, date, import_id, rate, flag FROM observations;
+    DROP TABLE series;
+    DROP TABLE observations;
+";
+
+const DATE_FORMAT: &str = "%Y-%m-%d";
+const TIMESTAMP_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+/// Changes made to one tenor by an import.
+#[derive(Debug, Clone, Default)]
+pub struct TenorImport {
+    pub added: usize,
+    pub updated: usize,
+    /// Stored observations missing from the imported range.
+    pub removed: usize,
+    pub unchanged: usize,
+}
+
+/// Outcome of merging a rate collection into the store.
+#[derive(Debug, Clone)]
+pub struct ImportSummary {
+    pub import_id: i64,
+    pub loaded_at: NaiveDateTime,
+    pub tenors: BTreeMap<Tenor, TenorImport>,
+}
+
+/// An import recorded in the store.
+#[derive(Debug, Clone)]
+pub struct ImportRecord {
+    pub id: i64,
+    pub source: String,
+    pub loaded_at: NaiveDateTime,
+}
+
+/// State of the store to read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Vintage {
+    /// Everything imported so far.
+    #[default]
+    Latest,
+    /// The state right after the import with this id.
+    Import(i64),
+    /// The state after the last import loaded at or before this time.
+    AsOf(NaiveDateTime),
+}
+
+impl fmt::Display for Vintage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Vintage::Latest => f.write_str("latest"),
+            Vintage::Import(id) => write!(f, "import {}", id),
+            Vintage::AsOf(time) => write!(f, "as of {}", time),
+        }
+    }
+}
+
+// Accepts "latest", an import id such as "3", a date (end of that day) or a timestamp
+impl FromStr for Vintage {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+        if s.eq_ignore_ascii_case("latest") {
+            return Ok(Vintage::Latest);
+        }
+        if let Ok(id) = s.trim_start_matches('#').parse::<i64>() {
+            return Ok(Vintage::Import(id));
+        }
+        if let Ok(time) = NaiveDateTime::parse_from_str(s, TIMESTAMP_FORMAT) {
+            return Ok(Vintage::AsOf(time));
+        }
+        match NaiveDate::parse_from_str(s, DATE_FORMAT) {
+            Ok(date) => Ok(Vintage::AsOf(date.and_time(NaiveTime::from_hms_opt(23, 59, 59).unwrap_or_default()))),
+            Err(_) => Err(Error::InvalidOption(format!("unknown vintage '{}'; expected latest, an import id or a date", s))),
+        }
+    }
+}
+
+/// How an observation differs between two vintages.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChangeKind {
+    Added { rate: f64 },
+    Removed { rate: f64 },
+    Changed { old: f64, new: f64 },
+}
+
+/// An observation that differs between two vintages.
+#[derive(Debug, Clone)]
+pub struct ObservationChange {
+    pub tenor: Tenor,
+    pub date: NaiveDate,
+    pub kind: ChangeKind,
+}
+
+/// An on-disk store of Euribor observations and their import history.
+pub struct RateStore {
+    conn: Connection,
+}
+
+// Parse a date column
+fn parse_date(value: &str) -> Result<NaiveDate> {
+    NaiveDate::parse_from_str(value, DATE_FORMAT)
+        .map_err(|e| Error::Date { value: value.to_string(), source: e })
+}
+
+// Parse a timestamp column
+fn parse_timestamp(value: &str) -> Result<NaiveDateTime> {
+    NaiveDateTime::parse_from_str(value, TIMESTAMP_FORMAT)
+        .map_err(|e| Error::Date { value: value.to_string(), source: e })
+}
+
+// Current value and flag of every observation of a tenor as of an import
+fn observations_as_of(conn: &Connection, code: &str, import_id: i64) -> Result<BTreeMap<NaiveDate, (f64, Option<String>)>> {
+    let mut statement = conn.prepare_cached(
+        "SELECT v.date, v.rate, v.flag FROM observation_versions v
+         WHERE v.tenor = ?1 AND v.import_id = (
+             SELECT MAX(w.import_id) FROM observation_versions w
+             WHERE w.tenor = v.tenor AND w.date = v.date AND w.import_id <= ?2)
+         ORDER BY v.date",
+    )?;
+    let rows = statement.query_map(params![code, import_id], |row| {
+        Ok((row.get::<_, String>(0)?, row.get::<_, Option<f64>>(1)?, row.get::<_, Option<String>>(2)?))
+    })?;
+
+    let mut observations = BTreeMap::new();
+    for row in rows {
+        if let (date, Some(rate), flag) = row? {
+            observations.insert(parse_date(&date)?, (rate, flag));
+        }
+    }
+    Ok(observations)
+}
+
+impl RateStore {
+    /// Open the store at `path`, creating or upgrading it as needed.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let conn = Connection::open(path)?;
+        let version: i64 = conn.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+        conn.execute_batch(SCHEMA)?;
+
+        if version < SCHEMA_VERSION {
+            let has_v1_tables: bool = conn.query_row(
+                "SELECT COUNT(*) > 0 FROM sqlite_master WHERE type = 'table' AND name = 'observations'",
+                [],
+                |row| row.get(0),
+            )?;
+            if has_v1_tables {
+                conn.execute_batch(MIGRATE_FROM_V1)?;
+            }
+            conn.execute_batch(&format!("PRAGMA user_version = {}", SCHEMA_VERSION))?;
+        }
+        Ok(RateStore { conn })
+    }
+
+    /// Merge the observations of `all_rates` into the store as a new vintage.
+    ///
+    /// New dates are added and changed values revised. Stored observations
+    /// that fall within the date range of an imported series but are missing
+    /// from it are marked as removed; older history is left alone, so partial
+    /// downloads can be imported incrementally.
+    pub fn import(&mut self, all_rates: &AllEuriborRates, source: &str) -> Result<ImportSummary> {
+        let previous = self.latest_import_id()?;
+        let now = Utc::now().naive_utc();
+        let loaded_at = now.with_nanosecond(0).unwrap_or(now);
+        let tx = self.conn.transaction()?;
+        tx.execute(
+            "INSERT INTO imports (source, loaded_at) VALUES (?1, ?2)",
+            params![source, loaded_at.format(TIMESTAMP_FORMAT).to_string()],
+        )?;
+        let import_id = tx.last_insert_rowid();
+        let mut tenors = BTreeMap::new();
+
+        for (tenor, series) in all_rates.iter_series() {
+            write_series_version(&tx, tenor, import_id, &series.metadata, source)?;
+            let counts = write_observation_versions(&tx, tenor, import_id, previous, &series.rates)?;
+            tenors.insert(tenor, counts);
+        }
+
+        tx.commit()?;
+        Ok(ImportSummary { import_id, loaded_at, tenors })
+    }
+
+    /// Every import in the order it was made.
+    pub fn imports(&self) -> Result<Vec<ImportRecord>> {
+        let mut statement = self.conn.prepare("SELECT id, source, loaded_at FROM imports ORDER BY id")?;
+        let rows = statement.query_map([], |row| {
+            Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?, row.get::<_, String>(2)?))
+        })?;
+
+        let mut imports = Vec::new();
+        for row in rows {
+            let (id, source, loaded_at) = row?;
+            imports.push(ImportRecord { id, source, loaded_at: parse_timestamp(&loaded_at)? });
+        }
+        Ok(imports)
+    }
+
+    // Id of the most recent import, or 0 for an empty store
+    fn latest_import_id(&self) -> Result<i64> {
+        Ok(self.conn.query_row("SELECT COALESCE(MAX(id), 0) FROM imports", [], |row| row.get(0))?)
+    }
+
+    /// Import id that a vintage refers to.
+    pub fn resolve(&self, vintage: Vintage) -> Result<i64> {
+        let id: Option<i64> = match vintage {
+            Vintage::Latest => Some(self.latest_import_id()?).filter(|&id| id > 0),
+            Vintage::Import(id) => self.conn.query_row(
+                "SELECT MAX(id) FROM imports WHERE id = ?1", [id], |row| row.get(0),
+            )?,
+            Vintage::AsOf(time) => self.conn.query_row(
+                "SELECT MAX(id) FROM imports WHERE loaded_at <= ?1",
+                [time.format(TIMESTAMP_FORMAT).to_string()],
+                |row| row.get(0),
+            )?,
+        };
+        id.ok_or_else(|| Error::NoData(format!("no import in the rate store matches {}", vintage)))
+    }
+
+    /// Read every stored series as of the latest import.
+    pub fn load(&self) -> Result<AllEuriborRates> {
+        self.load_vintage(Vintage::Latest)
+    }
+
+    /// Read every stored series as it was right after the given vintage.
+    pub fn load_vintage(&self, vintage: Vintage) -> Result<AllEuriborRates> {
+        let import_id = self.resolve(vintage)?;
+        let mut all_rates = AllEuriborRates::new();
+
+        let mut statement = self.conn.prepare(
+            "SELECT s.tenor, s.series_key, s.title, s.unit, s.unit_multiplier, s.last_update, s.source
+             FROM series_versions s
+             WHERE s.import_id = (
+                 SELECT MAX(t.import_id) FROM series_versions t WHERE t.tenor = s.tenor AND t.import_id <= ?1)",
+        )?;
+        let rows = statement.query_map([import_id], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                SeriesMetadata {
+                    series_key: row.get(1)?,
+                    title: row.get(2)?,
+                    unit: row.get(3)?,
+                    unit_multiplier: row.get(4)?,
+                    last_update: row.get::<_, Option<String>>(5)?
+                        .and_then(|t| NaiveDateTime::parse_from_str(&t, TIMESTAMP_FORMAT).ok()),
+                    source: row.get(6)?,
+                    ..SeriesMetadata::default()
+                },
+            ))
+        })?;
+
+        for row in rows {
+            let (code, metadata) = row?;
+            let tenor: Tenor = code.parse()?;
+            let rates = observations_as_of(&self.conn, &code, import_id)?
+                .into_iter()
+                .map(|(date, (rate, flag))| EuriborRate { date, rate, flag })
+                .collect();
+            all_rates.insert(tenor, Series { metadata, rates, gaps: Vec::new() });
+        }
+
+        if all_rates.is_empty() {
+            return Err(Error::NoData("the rate store is empty".to_string()));
+        }
+        Ok(all_rates)
+    }
+
+    /// Observations added, removed or changed between two vintages.
+    pub fn diff(&self, from: Vintage, to: Vintage) -> Result<Vec<ObservationChange>> {
+        let (from, to) = (self.resolve(from)?, self.resolve(to)?);
+        let mut statement = self.conn.prepare("SELECT DISTINCT tenor FROM observation_versions")?;
+        let codes: Vec<String> = statement.query_map([], |row| row.get(0))?.collect::<rusqlite::Result<_>>()?;
+        let mut tenors: Vec<Tenor> = codes.iter().map(|c| c.parse()).collect::<Result<_>>()?;
+        tenors.sort();
+
+        let mut changes = Vec::new();
+        for tenor in tenors {
+            let code = tenor.code();
+            let old = observations_as_of(&self.conn, &code, from)?;
+            let new = observations_as_of(&self.conn, &code, to)?;
+
+            let mut dates: Vec<&NaiveDate> = old.keys().chain(new.keys()).collect();
+            dates.sort();
+            dates.dedup();
+            for date in dates {
+                let kind = match (old.get(date), new.get(date)) {
+                    (None, Some((rate, _))) => ChangeKind::Added { rate: *rate },
+                    (Some((rate, _)), None) => ChangeKind::Removed { rate: *rate },
+                    (Some((old, _)), Some((new, _))) if old != new => ChangeKind::Changed { old: *old, new: *new },
+                    _ => continue,
+                };
+                changes.push(ObservationChange { tenor, date: *date, kind });
+            }
+        }
+        Ok(changes)
+    }
+}
+
+// Record the metadata of a series for an import
+fn write_series_version(tx: &Transaction, tenor: Tenor, import_id: i64, metadata: &SeriesMetadata, source: &str) -> Result<()> {
+    tx.execute(
+        "INSERT INTO series_versions (tenor, import_id, series_key, title, unit, unit_multiplier, last_update, source)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+        params![
+            tenor.code(), import_id, metadata.series_key, metadata.title, metadata.unit, metadata.unit_multiplier,
+            metadata.last_update.map(|t| t.format(TIMESTAMP_FORMAT).to_string()),
+            metadata.source.clone().or_else(|| Some(source.to_string())),
+        ],
+    )?;
+    Ok(())
+}
+
+// Store the observations that differ from the previous vintage
+fn write_observation_versions(tx: &Transaction, tenor: Tenor, import_id: i64, previous: i64, rates: &[EuriborRate]) -> Result<TenorImport> {
+    let code = tenor.code();
+    let current = observations_as_of(tx, &code, previous)?;
+    let mut insert = tx.prepare_cached(
+        "INSERT INTO observation_versions (tenor, date, import_id, rate, flag) VALUES (?1, ?2, ?3, ?4, ?5)",
+    )?;
+    let mut counts = TenorImport::default();
+
+    let imported: BTreeMap<NaiveDate, &EuriborRate> = rates.iter().map(|r| (r.date, r)).collect();
+    for (date, rate) in &imported {
+        match current.get(date) {
+            Some((value, flag)) if *value == rate.rate && *flag == rate.flag => {
+                counts.unchanged += 1;
+                continue;
+            }
+            Some(_) => counts.updated += 1,
+            None => counts.added += 1,
+        }
+        insert.execute(params![code, date.format(DATE_FORMAT).to_string(), import_id, rate.rate, rate.flag])?;
+    }
+
+    if let (Some(first), Some(last)) = (imported.keys().next(), imported.keys().next_back()) {
+        for date in current.range(first..=last).map(|(d, _)| d).filter(|d| !imported.contains_key(d)) {
+            insert.execute(params![code, date.format(DATE_FORMAT).to_string(), import_id, None::<f64>, None::<String>])?;
+            counts.removed += 1;
+        }
+    }
+    Ok(counts)
+}
+
+/// Observations read from a [`RateStore`] file.
+#[derive(Debug, Clone)]
+pub struct StoreSource {
+    pub path: PathBuf,
+    pub vintage: Vintage,
+}
+
+impl StoreSource {
+    pub fn new<P: Into<PathBuf>>(path: P, vintage: Vintage) -> Self {
+        StoreSource { path: path.into(), vintage }
+    }
+}
+
+impl RateSource for StoreSource {
+    fn name(&self) -> String {
+        format!("rate store ({}, {})", self.path.display(), self.vintage)
+    }
+
+    // The store holds observed values only, so the load options do not apply
+    fn load(&self, _options: &LoadOptions) -> Result<AllEuriborRates> {
+        RateStore::open(&self.path)
+            .and_then(|store| store.load_vintage(self.vintage))
+            .map_err(|e| Error::in_file(&self.path, e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 3, day).unwrap()
+    }
+
+    // A three-month series of the given (day of March 2024, rate) pairs
+    fn three_months(fixings: &[(u32, f64)]) -> AllEuriborRates {
+        let rates = fixings.iter().map(|&(day, rate)| EuriborRate { date: date(day), rate, flag: None }).collect();
+        let mut all_rates = AllEuriborRates::new();
+        all_rates.insert(Tenor::months(3), Series { rates, ..Series::default() });
+        all_rates
+    }
+
+    fn fixings(all_rates: &AllEuriborRates) -> Vec<(NaiveDate, f64)> {
+        all_rates.get(Tenor::months(3)).unwrap().iter().map(|r| (r.date, r.rate)).collect()
+    }
+
+    // A store with a first import of four days and a second one revising, removing and adding a day
+    fn revised_store() -> RateStore {
+        let mut store = RateStore::open(":memory:").unwrap();
+        store.import(&three_months(&[(4, 3.90), (5, 3.91), (6, 3.92), (7, 3.93)]), "first").unwrap();
+        store.import(&three_months(&[(4, 3.90), (6, 3.95), (7, 3.93), (8, 3.94)]), "second").unwrap();
+        store
+    }
+
+    #[test]
+    fn import_counts_added_revised_and_removed_observations() {
+        let mut store = RateStore::open(":memory:").unwrap();
+        let first = store.import(&three_months(&[(4, 3.90), (5, 3.91), (6, 3.92), (7, 3.93)]), "first").unwrap();
+        assert_eq!(first.tenors[&Tenor::months(3)].added, 4);
+
+        let second = store.import(&three_months(&[(4, 3.90), (6, 3.95), (7, 3.93), (8, 3.94)]), "second").unwrap();
+        let counts = &second.tenors[&Tenor::months(3)];
+        assert_eq!((counts.added, counts.updated, counts.removed, counts.unchanged), (1, 1, 1, 2));
+        assert_eq!(store.imports().unwrap().iter().map(|i| i.source.as_str()).collect::<Vec<_>>(), ["first", "second"]);
+
+        // Dates before the imported range are kept
+        let third = store.import(&three_months(&[(8, 3.94)]), "third").unwrap();
+        assert_eq!(third.tenors[&Tenor::months(3)].removed, 0);
+        assert_eq!(fixings(&store.load().unwrap()).len(), 4);
+    }
+
+    #[test]
+    fn vintages_restore_earlier_imports() {
+        let store = revised_store();
+        assert_eq!(
+            fixings(&store.load().unwrap()),
+            [(date(4), 3.90), (date(6), 3.95), (date(7), 3.93), (date(8), 3.94)]
+        );
+        assert_eq!(
+            fixings(&store.load_vintage(Vintage::Import(1)).unwrap()),
+            [(date(4), 3.90), (date(5), 3.91), (date(6), 3.92), (date(7), 3.93)]
+        );
+        assert!(store.load_vintage(Vintage::Import(3)).is_err());
+        assert_eq!("#2".parse::<Vintage>().unwrap(), Vintage::Import(2));
+    }
+
+    #[test]
+    fn diff_lists_changes_between_vintages() {
+        let store = revised_store();
+        let changes: Vec<(NaiveDate, ChangeKind)> = store.diff(Vintage::Import(1), Vintage::Latest).unwrap()
+            .into_iter()
+            .map(|c| (c.date, c.kind))
+            .collect();
+        assert_eq!(changes, [
+            (date(5), ChangeKind::Removed { rate: 3.91 }),
+            (date(6), ChangeKind::Changed { old: 3.92, new: 3.95 }),
+            (date(8), ChangeKind::Added { rate: 3.94 }),
+        ]);
+        assert!(store.diff(Vintage::Latest, Vintage::Latest).unwrap().is_empty());
+    }
+
+    #[test]
+    fn opening_a_version_1_store_migrates_it() {
+        let path = std::env::temp_dir().join(format!("euribor-store-v1-{}.db", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let conn = Connection::open(&path).unwrap();
+        conn.execute_batch("
+            CREATE TABLE imports (id INTEGER PRIMARY KEY, source TEXT NOT NULL, loaded_at TEXT NOT NULL);
+            CREATE TABLE series (tenor TEXT PRIMARY KEY, series_key TEXT, title TEXT, unit TEXT,
+                unit_multiplier TEXT, last_update TEXT, source TEXT);
+            CREATE TABLE observations (tenor TEXT NOT NULL, date TEXT NOT NULL, rate REAL NOT NULL, flag TEXT,
+                import_id INTEGER NOT NULL REFERENCES imports(id), PRIMARY KEY (tenor, date));
+            INSERT INTO imports VALUES (1, 'first', '2024-03-05 18:00:00'), (2, 'second', '2024-03-06 18:00:00');
+            INSERT INTO series VALUES ('M03', 'BBIG1.D.D0.EUR.MMKT.EURIBOR.M03.BID._Z', NULL, '% p.a.', NULL, NULL, 'first');
+            INSERT INTO observations VALUES
+                ('M03', '2024-03-04', 3.90, NULL, 1),
+                ('M03', '2024-03-05', 3.91, 'Provisional value', 1),
+                ('M03', '2024-03-06', 3.92, NULL, 2);
+        ").unwrap();
+        drop(conn);
+
+        let store = RateStore::open(&path).unwrap();
+        let latest = store.load().unwrap();
+        assert_eq!(fixings(&latest), [(date(4), 3.90), (date(5), 3.91), (date(6), 3.92)]);
+        let series = latest.series(Tenor::months(3)).unwrap();
+        assert_eq!(series.metadata.series_key.as_deref(), Some("BBIG1.D.D0.EUR.MMKT.EURIBOR.M03.BID._Z"));
+        assert_eq!(series.provisional_count(), 1);
+        assert_eq!(fixings(&store.load_vintage(Vintage::Import(1)).unwrap()), [(date(4), 3.90), (date(5), 3.91)]);
+        assert_eq!(store.conn.query_row("PRAGMA user_version", [], |row| row.get::<_, i64>(0)).unwrap(), SCHEMA_VERSION);
+
+        drop(store);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
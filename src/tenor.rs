@@ -0,0 +1,137 @@
+//! Euribor tenors as used in Bundesbank series keys (`W01`, `M03`, ...).
+
+use crate::calendar::add_months;
+use crate::error::Error;
+use chrono::{Duration, NaiveDate};
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+/// Unit of a tenor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TenorUnit {
+    Week,
+    Month,
+}
+
+/// Maturity of a Euribor fixing, e.g. one week or three months.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Tenor {
+    unit: TenorUnit,
+    count: u32,
+}
+
+impl Tenor {
+    /// The five tenors still published since December 2013.
+    pub const STANDARD: [Tenor; 5] = [
+        Tenor::weeks(1), Tenor::months(1), Tenor::months(3), Tenor::months(6), Tenor::months(12),
+    ];
+
+    /// Every tenor published at some point, including the ones discontinued in 2013.
+    pub const ALL: [Tenor; 15] = [
+        Tenor::weeks(1), Tenor::weeks(2), Tenor::weeks(3),
+        Tenor::months(1), Tenor::months(2), Tenor::months(3), Tenor::months(4),
+        Tenor::months(5), Tenor::months(6), Tenor::months(7), Tenor::months(8),
+        Tenor::months(9), Tenor::months(10), Tenor::months(11), Tenor::months(12),
+    ];
+
+    pub const fn weeks(count: u32) -> Tenor {
+        Tenor { unit: TenorUnit::Week, count }
+    }
+
+    pub const fn months(count: u32) -> Tenor {
+        Tenor { unit: TenorUnit::Month, count }
+    }
+
+    pub fn unit(&self) -> TenorUnit {
+        self.unit
+    }
+
+    pub fn count(&self) -> u32 {
+        self.count
+    }
+
+    /// Bundesbank series key code, e.g. `W01` or `M12`.
+    pub fn code(&self) -> String {
+        match self.unit {
+            TenorUnit::Week => format!("W{:02}", self.count),
+            TenorUnit::Month => format!("M{:02}", self.count),
+        }
+    }
+
+    /// Short label used in the chart legend, e.g. `1w` or `12m`.
+    pub fn label(&self) -> String {
+        match self.unit {
+            TenorUnit::Week => format!("{}w", self.count),
+            TenorUnit::Month => format!("{}m", self.count),
+        }
+    }
+
+    /// Reset period in days, counting a month as 30 days.
+    pub fn period_days(&self) -> i64 {
+        match self.unit {
+            TenorUnit::Week => 7 * self.count as i64,
+            TenorUnit::Month => 30 * self.count as i64,
+        }
+    }
+
+    /// Date `periods` tenors after `start`, using calendar months with the end-of-month rule.
+    ///
+    /// Counting from the start date rather than from the previous reset keeps
+    /// a schedule on its anniversary, e.g. 31 Jan, 29 Feb, 31 Mar for 1m.
+    pub fn advance(&self, start: NaiveDate, periods: u32) -> NaiveDate {
+        match self.unit {
+            TenorUnit::Week => start + Duration::weeks((self.count * periods) as i64),
+            TenorUnit::Month => add_months(start, self.count * periods),
+        }
+    }
+}
+
+impl Ord for Tenor {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.period_days().cmp(&other.period_days())
+            .then_with(|| (self.unit as u8).cmp(&(other.unit as u8)))
+            .then_with(|| self.count.cmp(&other.count))
+    }
+}
+
+impl PartialOrd for Tenor {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for Tenor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.label())
+    }
+}
+
+// Accepts series key codes ("W01", "M09") as well as labels ("1w", "9m").
+impl FromStr for Tenor {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let invalid = || Error::InvalidTenor(s.to_string());
+        let upper = s.to_ascii_uppercase();
+
+        let (unit, digits) = if let Some(digits) = upper.strip_prefix('W') {
+            (TenorUnit::Week, digits)
+        } else if let Some(digits) = upper.strip_prefix('M') {
+            (TenorUnit::Month, digits)
+        } else if let Some(digits) = upper.strip_suffix('W') {
+            (TenorUnit::Week, digits)
+        } else if let Some(digits) = upper.strip_suffix('M') {
+            (TenorUnit::Month, digits)
+        } else {
+            return Err(invalid());
+        };
+
+        let count: u32 = digits.parse().map_err(|_| invalid())?;
+        if count == 0 {
+            return Err(invalid());
+        }
+        Ok(Tenor { unit, count })
+    }
+}
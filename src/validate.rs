@@ -0,0 +1,186 @@
+//! Plausibility checks on loaded Euribor series.
+
+use crate::calendar::{is_weekend, target_holiday};
+use crate::rates::AllEuriborRates;
+use crate::tenor::Tenor;
+use chrono::NaiveDate;
+use std::fmt;
+
+/// Limits applied by [`validate`].
+#[derive(Debug, Clone)]
+pub struct ValidationOptions {
+    /// Lowest plausible rate in percent.
+    pub min_rate: f64,
+    /// Highest plausible rate in percent.
+    pub max_rate: f64,
+    /// Largest plausible change between consecutive fixings, in percentage points.
+    pub max_jump: f64,
+    /// Longest run of calendar days without a fixing while other tenors have data.
+    pub max_gap_days: i64,
+}
+
+impl Default for ValidationOptions {
+    fn default() -> Self {
+        ValidationOptions { min_rate: -2.0, max_rate: 20.0, max_jump: 0.5, max_gap_days: 10 }
+    }
+}
+
+/// Kind of problem found in a series.
+#[derive(Debug, Clone, PartialEq)]
+pub enum IssueKind {
+    /// The date appears more than once.
+    DuplicateDate,
+    /// The date comes after a later one.
+    OutOfOrder { previous: NaiveDate },
+    /// The observation falls on a weekend.
+    Weekend,
+    /// The observation falls on a TARGET holiday.
+    Holiday(&'static str),
+    /// The rate moved more than the allowed jump since the previous fixing.
+    Jump { previous: f64, rate: f64 },
+    /// The rate lies outside the plausible range.
+    OutOfRange { rate: f64 },
+    /// No fixing since `start` although other tenors have `others` fixings in between.
+    CurveGap { start: NaiveDate, days: i64, others: usize },
+}
+
+/// A problem found in one series.
+#[derive(Debug, Clone)]
+pub struct Issue {
+    pub tenor: Tenor,
+    pub date: NaiveDate,
+    pub kind: IssueKind,
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}: ", self.tenor, self.date)?;
+        match &self.kind {
+            IssueKind::DuplicateDate => write!(f, "duplicate date"),
+            IssueKind::OutOfOrder { previous } => write!(f, "out of order after {}", previous),
+            IssueKind::Weekend => write!(f, "observation on a weekend"),
+            IssueKind::Holiday(name) => write!(f, "observation on a TARGET holiday ({})", name),
+            IssueKind::Jump { previous, rate } =>
+                write!(f, "jump from {} to {} ({:+.3} pp)", previous, rate, rate - previous),
+            IssueKind::OutOfRange { rate } => write!(f, "rate {} outside the plausible range", rate),
+            IssueKind::CurveGap { start, days, others } =>
+                write!(f, "no fixing for {} days since {} while other tenors have {} fixings", days, start, others),
+        }
+    }
+}
+
+/// Check every series and return the problems found, grouped by tenor in date order.
+pub fn validate(all_rates: &AllEuriborRates, options: &ValidationOptions) -> Vec<Issue> {
+    let mut issues = Vec::new();
+
+    for (tenor, rates) in all_rates.iter() {
+        let mut tenor_issues = Vec::new();
+        let mut issue = |date, kind| tenor_issues.push(Issue { tenor, date, kind });
+
+        let mut last: Option<(NaiveDate, f64)> = None;
+        for rate in rates {
+            if is_weekend(rate.date) {
+                issue(rate.date, IssueKind::Weekend);
+            } else if let Some(name) = target_holiday(rate.date) {
+                issue(rate.date, IssueKind::Holiday(name));
+            }
+            if !(options.min_rate..=options.max_rate).contains(&rate.rate) {
+                issue(rate.date, IssueKind::OutOfRange { rate: rate.rate });
+            }
+
+            // Jumps are measured against the latest fixing in date order
+            match last {
+                Some((date, _)) if date == rate.date => issue(rate.date, IssueKind::DuplicateDate),
+                Some((date, _)) if date > rate.date => issue(rate.date, IssueKind::OutOfOrder { previous: date }),
+                _ => {
+                    if let Some((_, previous)) = last {
+                        if (rate.rate - previous).abs() > options.max_jump {
+                            issue(rate.date, IssueKind::Jump { previous, rate: rate.rate });
+                        }
+                    }
+                    last = Some((rate.date, rate.rate));
+                }
+            }
+        }
+
+        // Interior gaps in this tenor that other tenors have data for
+        let mut dates: Vec<NaiveDate> = rates.iter().map(|r| r.date).collect();
+        dates.sort();
+        dates.dedup();
+        for pair in dates.windows(2) {
+            let days = (pair[1] - pair[0]).num_days();
+            if days <= options.max_gap_days {
+                continue;
+            }
+            let others = all_rates.iter()
+                .filter(|(other, _)| *other != tenor)
+                .flat_map(|(_, rates)| rates)
+                .filter(|r| r.date > pair[0] && r.date < pair[1])
+                .count();
+            if others > 0 {
+                issue(pair[1], IssueKind::CurveGap { start: pair[0], days, others });
+            }
+        }
+
+        tenor_issues.sort_by_key(|i| i.date);
+        issues.append(&mut tenor_issues);
+    }
+
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rates::{EuriborRate, Series};
+
+    fn series(fixings: &[(u32, u32, f64)]) -> Series {
+        let rates = fixings.iter()
+            .map(|&(month, day, rate)| EuriborRate { date: date(month, day), rate, flag: None })
+            .collect();
+        Series { rates, ..Series::default() }
+    }
+
+    fn date(month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, month, day).unwrap()
+    }
+
+    #[test]
+    fn every_issue_kind_is_reported() {
+        let mut all_rates = AllEuriborRates::new();
+        all_rates.insert(Tenor::months(3), series(&[
+            (1, 2, 3.90),
+            (1, 3, 3.90),
+            (1, 3, 3.90),
+            (1, 2, 3.90),
+            (1, 6, 3.90),
+            (1, 8, 4.60),
+            (1, 22, 4.60),
+            (3, 29, 4.60),
+        ]));
+        // A single fixing inside the 3m gap, below the plausible range
+        all_rates.insert(Tenor::months(12), series(&[(1, 15, -3.0)]));
+
+        let issues: Vec<(Tenor, NaiveDate, IssueKind)> = validate(&all_rates, &ValidationOptions::default())
+            .into_iter()
+            .map(|i| (i.tenor, i.date, i.kind))
+            .collect();
+        let three_months = Tenor::months(3);
+        assert_eq!(issues, [
+            (three_months, date(1, 2), IssueKind::OutOfOrder { previous: date(1, 3) }),
+            (three_months, date(1, 3), IssueKind::DuplicateDate),
+            (three_months, date(1, 6), IssueKind::Weekend),
+            (three_months, date(1, 8), IssueKind::Jump { previous: 3.90, rate: 4.60 }),
+            (three_months, date(1, 22), IssueKind::CurveGap { start: date(1, 8), days: 14, others: 1 }),
+            (three_months, date(3, 29), IssueKind::Holiday("Good Friday")),
+            (Tenor::months(12), date(1, 15), IssueKind::OutOfRange { rate: -3.0 }),
+        ]);
+    }
+
+    #[test]
+    fn clean_series_has_no_issues() {
+        let mut all_rates = AllEuriborRates::new();
+        all_rates.insert(Tenor::months(3), series(&[(3, 27, 3.92), (3, 28, 3.91), (4, 2, 3.89), (4, 3, 3.90)]));
+        assert!(validate(&all_rates, &ValidationOptions::default()).is_empty());
+    }
+}
@@ -0,0 +1,233 @@
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use euribor_cost_chart::average::reference_average_rates;
+use euribor_cost_chart::projection::project;
+use euribor_cost_chart::{
+    calculate_average_rates, scenario_average_rates, AllEuriborRates, AverageOptions,
+    AveragingMethod, DayCount, Direction, EuriborRate, FixingLookup, Projection, Scenario, Series,
+    TailPolicy, Tenor,
+};
+
+// Weekday fixings of the standard tenors from 2019 to mid-2024, with a few days missing
+fn synthetic_rates() -> AllEuriborRates {
+    let start = NaiveDate::from_ymd_opt(2019, 1, 2).unwrap();
+    let mut all_rates = AllEuriborRates::new();
+
+    for (i, tenor) in Tenor::STANDARD.into_iter().enumerate() {
+        let rates = (0..2000)
+            .map(|day| start + Duration::days(day))
+            .filter(|date| !matches!(date.weekday(), Weekday::Sat | Weekday::Sun))
+            .filter(|date| date.ordinal() % 37 != 5)
+            .map(|date| {
+                let t = (date - start).num_days() as f64;
+                EuriborRate { date, rate: 1.0 + 0.2 * i as f64 + 2.0 * (t / 300.0).sin(), flag: None }
+            })
+            .collect();
+        all_rates.insert(tenor, Series { rates, ..Series::default() });
+    }
+    all_rates
+}
+
+#[test]
+fn fast_engine_matches_reference() {
+    let all_rates = synthetic_rates();
+    let cases = [
+        AverageOptions::default(),
+        AverageOptions {
+            fixing_lookup: FixingLookup::Lag(2),
+            day_count: DayCount::Act365,
+            method: AveragingMethod::Compounded,
+            direction: Direction::Forward,
+            ..AverageOptions::default()
+        },
+        AverageOptions {
+            fixing_lookup: "modified-following".parse().unwrap(),
+            day_count: DayCount::Thirty360,
+            method: AveragingMethod::Simple,
+            per_actual_year: true,
+            direction: Direction::Trailing,
+            ..AverageOptions::default()
+        },
+        AverageOptions { method: AveragingMethod::Compounded, direction: Direction::Trailing, ..AverageOptions::default() },
+    ];
+
+    for options in &cases {
+        let windows = [1, 30, 360, 1100];
+        let all_fast = calculate_average_rates(&all_rates, &windows, options).unwrap();
+        assert_eq!(all_fast.len(), windows.len());
+
+        for (fast, window) in all_fast.iter().zip(windows) {
+            let reference = reference_average_rates(&all_rates, window, options).unwrap();
+            assert_eq!(fast.averaged_time_days, window);
+            assert_eq!(fast.start_date, reference.start_date);
+
+            for (tenor, expected) in &reference.averages {
+                let actual = &fast.averages[tenor];
+                assert_eq!(actual.len(), expected.len());
+                for (day, (a, e)) in actual.iter().zip(expected).enumerate() {
+                    assert!((a - e).abs() < 1e-9, "{} {:?} window {} day {}: {} != {}", tenor, options, window, day, a, e);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn unchanged_scenario_matches_flat_projection() {
+    let all_rates = synthetic_rates();
+    let options = AverageOptions { tail: TailPolicy::Project, ..AverageOptions::default() };
+    let flat = calculate_average_rates(&all_rates, &[360], &options).unwrap();
+
+    let scenario = Scenario::parse("unchanged", b"date,tenor,shift_bp\n2025-06-30,,0\n").unwrap();
+    let shifted = scenario_average_rates(&all_rates, &scenario, &[360], &AverageOptions::default()).unwrap();
+    assert_eq!(shifted[0].scenario.as_deref(), Some("unchanged"));
+    assert_eq!(shifted[0].averaged_time_mark, flat[0].averaged_time_mark);
+    assert_eq!(shifted[0].averages, flat[0].averages);
+
+    // A rate path held at 3% raises the cost of windows reaching past the data
+    let scenario = Scenario::parse("hike", b"# comment\ndate,tenor,rate\n2024-12-31,M03,3.0\n").unwrap();
+    let hiked = scenario_average_rates(&all_rates, &scenario, &[360], &AverageOptions::default()).unwrap();
+    let (flat_3m, hiked_3m) = (&flat[0].averages[&Tenor::months(3)], &hiked[0].averages[&Tenor::months(3)]);
+    assert_eq!(flat_3m.len(), hiked_3m.len());
+    assert!(hiked_3m.last().unwrap() > flat_3m.last().unwrap());
+    assert_eq!(flat[0].averages[&Tenor::months(6)], hiked[0].averages[&Tenor::months(6)]);
+}
+
+#[test]
+fn discontinued_tenor_ends_at_its_last_fixing() {
+    let mut all_rates = synthetic_rates();
+    let end = NaiveDate::from_ymd_opt(2020, 6, 30).unwrap();
+    let mut two_weeks = all_rates.series(Tenor::weeks(1)).unwrap().clone();
+    two_weeks.rates.retain(|r| r.date <= end);
+    all_rates.insert(Tenor::weeks(2), two_weeks);
+
+    let start = all_rates.start_date().unwrap();
+    let fast = &calculate_average_rates(&all_rates, &[360], &AverageOptions::default()).unwrap()[0];
+    let reference = reference_average_rates(&all_rates, 360, &AverageOptions::default()).unwrap();
+    let two_weeks = &fast.averages[&Tenor::weeks(2)];
+    assert_eq!(fast.dates().nth(two_weeks.len() - 1), Some(end));
+    let expected = &reference.averages[&Tenor::weeks(2)];
+    assert_eq!(expected.len(), two_weeks.len());
+    assert!(two_weeks.iter().zip(expected).all(|(a, b)| (a - b).abs() < 1e-9));
+    assert_eq!(fast.time_mark(Tenor::weeks(2)), end - Duration::days(360));
+    assert_eq!(fast.time_mark(Tenor::months(3)), fast.averaged_time_mark);
+
+    let options = AverageOptions { tail: TailPolicy::Truncate, ..AverageOptions::default() };
+    let truncated = &calculate_average_rates(&all_rates, &[360], &options).unwrap()[0];
+    let len = (end - Duration::days(360) - start).num_days() as usize + 1;
+    assert_eq!(truncated.averages[&Tenor::weeks(2)].len(), len);
+}
+
+#[test]
+fn late_tenor_starts_at_its_first_fixing() {
+    let mut all_rates = synthetic_rates();
+    let start = NaiveDate::from_ymd_opt(2020, 1, 2).unwrap();
+    let mut two_weeks = all_rates.series(Tenor::weeks(1)).unwrap().clone();
+    two_weeks.rates.retain(|r| r.date >= start);
+    let mut alone = AllEuriborRates::new();
+    alone.insert(Tenor::weeks(2), two_weeks.clone());
+    all_rates.insert(Tenor::weeks(2), two_weeks);
+
+    for direction in [Direction::Forward, Direction::Trailing] {
+        let options = AverageOptions { direction, ..AverageOptions::default() };
+        let fast = &calculate_average_rates(&all_rates, &[360], &options).unwrap()[0];
+        let expected = &calculate_average_rates(&alone, &[360], &options).unwrap()[0];
+        let reference = reference_average_rates(&all_rates, 360, &options).unwrap();
+        assert_eq!(fast.first_date(Tenor::weeks(2)), start);
+        assert_eq!(fast.tenor_dates(Tenor::weeks(2)).next(), Some(start));
+        let two_weeks = &fast.averages[&Tenor::weeks(2)];
+        assert_eq!(two_weeks.len(), expected.averages[&Tenor::weeks(2)].len());
+        assert!(two_weeks.iter().zip(&expected.averages[&Tenor::weeks(2)]).all(|(a, b)| (a - b).abs() < 1e-9));
+        assert!(two_weeks.iter().zip(&reference.averages[&Tenor::weeks(2)]).all(|(a, b)| (a - b).abs() < 1e-9));
+        assert_eq!(fast.time_mark(Tenor::weeks(2)), expected.averaged_time_mark);
+    }
+
+    let options = AverageOptions { direction: Direction::Trailing, tail: TailPolicy::Truncate, ..AverageOptions::default() };
+    let truncated = &calculate_average_rates(&all_rates, &[360], &options).unwrap()[0];
+    assert_eq!(truncated.first_date(Tenor::weeks(2)), start + Duration::days(359));
+    assert_eq!(truncated.first_date(Tenor::months(3)), truncated.start_date);
+}
+
+#[test]
+fn constant_fixing_averages_to_itself() {
+    let mut all_rates = synthetic_rates();
+    for tenor in Tenor::STANDARD {
+        let mut series = all_rates.series(tenor).unwrap().clone();
+        series.rates.iter_mut().for_each(|r| r.rate = 3.0);
+        all_rates.insert(tenor, series);
+    }
+
+    for day_count in [DayCount::Act360, DayCount::Act365, DayCount::Thirty360] {
+        let options = AverageOptions { day_count, ..AverageOptions::default() };
+        for averages in calculate_average_rates(&all_rates, &[30, 360], &options).unwrap() {
+            assert!(averages.averages.values().flatten().all(|a| (a - 3.0).abs() < 1e-9), "{}", day_count);
+        }
+    }
+
+    // The effective cost of ACT/360 interest per 365-day year is higher
+    let options = AverageOptions { per_actual_year: true, ..AverageOptions::default() };
+    let averages = &calculate_average_rates(&all_rates, &[360], &options).unwrap()[0].averages[&Tenor::months(3)];
+    assert!((averages[0] - 3.0 * 365.0 / 360.0).abs() < 1e-9);
+}
+
+#[test]
+fn compounded_average_matches_hand_computed_rollover() {
+    // A constant 4% 3m fixing on every day of 2023 and 2024
+    let start = NaiveDate::from_ymd_opt(2023, 1, 2).unwrap();
+    let rates = (0..730)
+        .map(|day| EuriborRate { date: start + Duration::days(day), rate: 4.0, flag: None })
+        .collect();
+    let mut all_rates = AllEuriborRates::new();
+    all_rates.insert(Tenor::months(3), Series { rates, ..Series::default() });
+
+    // A loan from 2 Jan 2023 over 360 days resets on 2 Apr, 2 Jul and 2 Oct
+    // and its last period runs to 2 Jan 2024: 90, 91, 92 and 92 days at ACT/360
+    let year_fractions = [90.0 / 360.0, 91.0 / 360.0, 92.0 / 360.0, 92.0 / 360.0];
+    let growth: f64 = year_fractions.iter().map(|yf| 1.0 + 0.04 * yf).product();
+    let expected = (growth.powf(1.0 / year_fractions.iter().sum::<f64>()) - 1.0) * 100.0;
+    assert!(expected > 4.0);
+
+    let options = AverageOptions { method: AveragingMethod::Compounded, ..AverageOptions::default() };
+    let averages = &calculate_average_rates(&all_rates, &[360], &options).unwrap()[0].averages[&Tenor::months(3)];
+    assert!((averages[0] - expected).abs() < 1e-12, "{} != {}", averages[0], expected);
+}
+
+#[test]
+fn trailing_average_matches_hand_computed_window() {
+    // A 1m fixing equal to the month number on every day of 2023
+    let start = NaiveDate::from_ymd_opt(2023, 1, 1).unwrap();
+    let rates = (0..365)
+        .map(|day| start + Duration::days(day))
+        .map(|date| EuriborRate { date, rate: date.month() as f64, flag: None })
+        .collect();
+    let mut all_rates = AllEuriborRates::new();
+    all_rates.insert(Tenor::months(1), Series { rates, ..Series::default() });
+
+    // The 90 days ending on 30 Jun 2023 start on 2 Apr and reset on 2 May and
+    // 2 Jun: 30 days at 4%, 31 days at 5% and 29 days at 6%
+    let expected = (30.0 * 4.0 + 31.0 * 5.0 + 29.0 * 6.0) / 90.0;
+    let options = AverageOptions { direction: Direction::Trailing, ..AverageOptions::default() };
+    let averages = &calculate_average_rates(&all_rates, &[90], &options).unwrap()[0];
+    let day = averages.dates().position(|d| d == NaiveDate::from_ymd_opt(2023, 6, 30).unwrap()).unwrap();
+    assert!((averages.averages[&Tenor::months(1)][day] - expected).abs() < 1e-12);
+}
+
+#[test]
+fn discontinued_tenor_is_not_projected() {
+    let mut all_rates = synthetic_rates();
+    let end = NaiveDate::from_ymd_opt(2020, 6, 30).unwrap();
+    let mut two_weeks = all_rates.series(Tenor::weeks(1)).unwrap().clone();
+    two_weeks.rates.retain(|r| r.date <= end);
+    all_rates.insert(Tenor::weeks(2), two_weeks);
+
+    let until = all_rates.end_date().unwrap() + Duration::days(400);
+    for projection in [Projection::Flat, Projection::ForwardCurve] {
+        let projected = project(&all_rates, projection, until).unwrap();
+        assert_eq!(projected.get(Tenor::weeks(2)).unwrap().last().unwrap().date, end);
+        assert!(projected.get(Tenor::months(3)).unwrap().last().unwrap().date > until - Duration::days(5));
+    }
+
+    let options = AverageOptions { tail: TailPolicy::Project, ..AverageOptions::default() };
+    let projected = &calculate_average_rates(&all_rates, &[360], &options).unwrap()[0];
+    let partial = &calculate_average_rates(&all_rates, &[360], &AverageOptions::default()).unwrap()[0];
+    assert_eq!(projected.averages[&Tenor::weeks(2)], partial.averages[&Tenor::weeks(2)]);
+}
@@ -0,0 +1,69 @@
+use euribor_cost_chart::{fetch_series, FetchStatus, LoadOptions, SeriesKey};
+use std::fs;
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::path::PathBuf;
+use std::thread;
+
+fn fixture(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures").join(name)
+}
+
+// Serve each payload in turn to one request and return the base URL
+fn serve(payloads: Vec<Vec<u8>>) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+    thread::spawn(move || {
+        for payload in payloads {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = Vec::new();
+            let mut buffer = [0; 1024];
+            while !request.ends_with(b"\r\n\r\n") {
+                let n = stream.read(&mut buffer).unwrap();
+                if n == 0 {
+                    break;
+                }
+                request.extend_from_slice(&buffer[..n]);
+            }
+            let head = format!("HTTP/1.1 200 OK\r\nContent-Type: text/csv\r\nContent-Length: {}\r\nConnection: close\r\n\r\n", payload.len());
+            stream.write_all(head.as_bytes()).unwrap();
+            stream.write_all(&payload).unwrap();
+        }
+    });
+    format!("http://{}/rest/download", address)
+}
+
+#[test]
+fn fetch_reports_new_unchanged_and_changed_series() {
+    let key: SeriesKey = "BBIG1.D.D0.EUR.MMKT.EURIBOR.M03.BID._Z".parse().unwrap();
+    let original = fs::read_to_string(fixture(&key.file_name())).unwrap();
+    let revised = original
+        .replace("2024-01-03,3.946,", "2024-01-03,3.900,")
+        .replace("2024-01-04,3.942,\n", "")
+        + "2024-03-01,3.780,\n";
+    let base_url = serve(vec![
+        original.clone().into_bytes(),
+        original.clone().into_bytes(),
+        revised.clone().into_bytes(),
+        b"<html>Service unavailable</html>".to_vec(),
+    ]);
+
+    let dir = std::env::temp_dir().join(format!("euribor-fetch-{}", std::process::id()));
+    let options = LoadOptions::default();
+    let results: Vec<_> = (0..4).map(|_| fetch_series(&base_url, &key, &dir, &options)).collect();
+    let stored = fs::read_to_string(dir.join(key.file_name()));
+    fs::remove_dir_all(&dir).unwrap();
+
+    let statuses: Vec<_> = results[..3].iter().map(|r| r.as_ref().unwrap().status.clone()).collect();
+    assert_eq!(statuses, [
+        FetchStatus::New,
+        FetchStatus::Unchanged,
+        FetchStatus::Changed { added: 1, revised: 1, removed: 1 },
+    ]);
+    let last = results[2].as_ref().unwrap();
+    assert_eq!(last.last_date.unwrap().to_string(), "2024-03-01");
+
+    // The malformed payload is rejected and the revised file kept
+    assert!(results[3].is_err());
+    assert_eq!(stored.unwrap(), revised);
+}
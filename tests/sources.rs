@@ -0,0 +1,216 @@
+use chrono::NaiveDate;
+use euribor_cost_chart::{
+    calculate_average_rates, discover, read_csv, AllEuriborRates, AverageOptions, BundesbankBulkSource,
+    BundesbankSource, CsvDialect, EcbSource, EmmiSource, Frequency, LoadOptions, MissingValuePolicy,
+    RateSource, Series, Tenor,
+};
+use euribor_cost_chart::ecb::tenor_from_ecb_key;
+use euribor_cost_chart::emmi::{parse_emmi_csv, tenor_from_label};
+use euribor_cost_chart::loader::decode_text;
+use std::fs;
+use std::path::PathBuf;
+
+fn fixture(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures").join(name)
+}
+
+fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(y, m, d).unwrap()
+}
+
+fn sources() -> Vec<Box<dyn RateSource>> {
+    vec![
+        Box::new(BundesbankSource::new(vec![(Tenor::months(3), fixture("BBIG1.D.D0.EUR.MMKT.EURIBOR.M03.BID._Z.csv"))])),
+        Box::new(BundesbankSource::new(vec![(Tenor::months(3), fixture("bundesbank_de.csv"))])),
+        Box::new(BundesbankSource::new(vec![(Tenor::months(3), fixture("BBIG1.D.D0.EUR.MMKT.EURIBOR.M03.BID._Z.csv.gz"))])),
+        Box::new(BundesbankSource::new(vec![(Tenor::months(3), fixture("bundesbank_m03.zip"))])),
+        Box::new(BundesbankBulkSource::new(fixture("bundesbank_bulk.csv"))),
+        Box::new(EcbSource::new(fixture("ecb_portal.csv"))),
+        Box::new(EcbSource::new(fixture("ecb_sdmx.csv"))),
+        Box::new(EmmiSource::new(vec![fixture("emmi_hist_2024.csv")])),
+        Box::new(EmmiSource::new(vec![fixture("emmi_daily_2024.csv")])),
+    ]
+}
+
+fn drop_missing() -> LoadOptions {
+    LoadOptions { missing_value_policy: MissingValuePolicy::Drop, ..LoadOptions::default() }
+}
+
+#[test]
+fn every_source_loads_the_same_three_month_series() {
+    let reference = sources()[0].load(&drop_missing()).unwrap();
+    let expected = reference.get(Tenor::months(3)).unwrap();
+    assert_eq!(expected.len(), 42);
+    assert_eq!(expected[0].date, date(2024, 1, 2));
+    assert_eq!(expected[0].rate, 3.95);
+    assert_eq!(expected.last().unwrap().date, date(2024, 2, 29));
+
+    for source in sources() {
+        let loaded = source.load(&drop_missing()).unwrap();
+        let rates = loaded.get(Tenor::months(3)).unwrap_or_else(|| panic!("{} has no 3m series", source.name()));
+        let actual: Vec<(NaiveDate, f64)> = rates.iter().map(|r| (r.date, r.rate)).collect();
+        let wanted: Vec<(NaiveDate, f64)> = expected.iter().map(|r| (r.date, r.rate)).collect();
+        assert_eq!(actual, wanted, "{}", source.name());
+    }
+}
+
+#[test]
+fn averages_do_not_depend_on_the_source() {
+    let averages = |all_rates: &AllEuriborRates| {
+        calculate_average_rates(all_rates, &[30], &AverageOptions::default()).unwrap()[0].averages[&Tenor::months(3)].clone()
+    };
+    let reference = averages(&sources()[0].load(&drop_missing()).unwrap());
+
+    for source in sources() {
+        let mut loaded = source.load(&drop_missing()).unwrap();
+        loaded.retain(|tenor| tenor == Tenor::months(3));
+        assert_eq!(averages(&loaded), reference, "{}", source.name());
+    }
+}
+
+#[test]
+fn german_export_is_detected_and_decoded() {
+    let data = std::fs::read(fixture("bundesbank_de.csv")).unwrap();
+    assert_eq!(CsvDialect::detect(&decode_text(&data)), CsvDialect::German);
+
+    let series = read_csv(fixture("bundesbank_de.csv"), &LoadOptions::default()).unwrap();
+    // Latin-1 umlauts in the header and the flags survive decoding
+    assert_eq!(series.metadata.title.as_deref(), Some("Geldmarktsätze / EURIBOR / Dreimonatsgeld / Tageswerte"));
+    assert_eq!(series.metadata.last_update, Some(date(2024, 3, 1).and_hms_opt(8, 47, 5).unwrap()));
+    assert_eq!(series.gaps.len(), 1);
+    assert_eq!(series.provisional_count(), 1);
+    assert_eq!(series.rates.last().unwrap().flag.as_deref(), Some("Vorläufiger Wert"));
+}
+
+#[test]
+fn bulk_export_pairs_values_with_their_flags() {
+    let loaded = BundesbankBulkSource::new(fixture("bundesbank_bulk.csv")).load(&LoadOptions::default()).unwrap();
+    // The EONIA columns between the two Euribor series are skipped
+    assert_eq!(loaded.tenors().collect::<Vec<_>>(), vec![Tenor::months(3), Tenor::months(12)]);
+
+    let three_months = loaded.series(Tenor::months(3)).unwrap();
+    let twelve_months = loaded.series(Tenor::months(12)).unwrap();
+    assert_eq!(three_months.metadata.title.as_deref(), Some("Money market rates / EURIBOR / Three-month funds / Daily data"));
+    assert_eq!(twelve_months.metadata.title.as_deref(), Some("Money market rates / EURIBOR / Twelve-month funds / Daily data"));
+    assert_eq!(twelve_months.rates[0].rate, 3.5);
+
+    // Each series keeps its own gaps and flags
+    assert_eq!(three_months.gaps.len(), 1);
+    assert!(twelve_months.gaps.is_empty());
+    let flag = |series: &Series, day: NaiveDate| series.rates.iter().find(|r| r.date == day).unwrap().flag.clone();
+    assert_eq!(flag(three_months, date(2024, 2, 1)), None);
+    assert_eq!(flag(twelve_months, date(2024, 2, 1)).as_deref(), Some("Corrected value"));
+    assert_eq!(three_months.provisional_count(), 1);
+    assert_eq!(twelve_months.provisional_count(), 1);
+}
+
+#[test]
+fn ecb_portal_maps_series_keys_to_tenors() {
+    let loaded = EcbSource::new(fixture("ecb_portal.csv")).load(&LoadOptions::default()).unwrap();
+    assert_eq!(loaded.tenors().collect::<Vec<_>>(), vec![Tenor::months(3), Tenor::months(12)]);
+
+    let series = loaded.series(Tenor::months(12)).unwrap();
+    assert_eq!(series.metadata.series_key.as_deref(), Some("FM.D.U2.EUR.RT.MM.EURIBOR1YD_.HSTA"));
+    assert_eq!(series.metadata.source.as_deref(), Some("ECB Data Portal"));
+    // Rows are newest first in the download and "NaN" marks the missing fixing
+    assert!(series.rates.windows(2).all(|w| w[0].date < w[1].date));
+    assert_eq!(series.gaps.len(), 1);
+    assert_eq!(series.gaps[0].start, date(2024, 1, 17));
+    assert!(series.gaps[0].filled);
+}
+
+#[test]
+fn ecb_sdmx_status_becomes_observation_flag() {
+    let loaded = EcbSource::new(fixture("ecb_sdmx.csv")).load(&LoadOptions::default()).unwrap();
+    let series = loaded.series(Tenor::months(3)).unwrap();
+    assert_eq!(series.metadata.title.as_deref(), Some("Euribor 3-month - Historical close"));
+    assert_eq!(series.provisional_count(), 1);
+    assert!(series.rates.last().unwrap().is_provisional());
+}
+
+#[test]
+fn emmi_layouts_agree() {
+    let by_tenor = EmmiSource::new(vec![fixture("emmi_hist_2024.csv")]).load(&LoadOptions::default()).unwrap();
+    let by_date = EmmiSource::new(vec![fixture("emmi_daily_2024.csv")]).load(&LoadOptions::default()).unwrap();
+
+    for tenor in [Tenor::months(3), Tenor::months(12)] {
+        let a: Vec<(NaiveDate, f64)> = by_tenor.get(tenor).unwrap().iter().map(|r| (r.date, r.rate)).collect();
+        let b: Vec<(NaiveDate, f64)> = by_date.get(tenor).unwrap().iter().map(|r| (r.date, r.rate)).collect();
+        assert_eq!(a.len(), 43);
+        assert_eq!(a, b);
+    }
+}
+
+#[test]
+fn missing_value_policy_error_applies_to_every_source() {
+    let options = LoadOptions { missing_value_policy: MissingValuePolicy::Error, ..LoadOptions::default() };
+    // The SDMX fixture omits the missing day rather than marking it
+    for source in sources().iter().filter(|s| !s.name().contains("ecb_sdmx")) {
+        assert!(source.load(&options).is_err(), "{}", source.name());
+    }
+}
+
+#[test]
+fn discovery_separates_series_files_from_other_fixtures() {
+    let dir = fixture("");
+    let discovery = discover(&dir).unwrap();
+
+    let mut locations: Vec<String> = discovery.files.iter()
+        .map(|f| f.location().trim_start_matches(dir.to_str().unwrap()).to_string())
+        .collect();
+    locations.sort();
+    assert_eq!(locations, [
+        "BBIG1.D.D0.EUR.MMKT.EURIBOR.M03.BID._Z.csv",
+        "BBIG1.D.D0.EUR.MMKT.EURIBOR.M03.BID._Z.csv.gz",
+        "bundesbank_m03.zip!BBIG1.D.D0.EUR.MMKT.EURIBOR.M03.BID._Z.csv",
+    ]);
+    assert!(discovery.files.iter().all(|f| f.key.tenor == Tenor::months(3) && f.key.frequency == Frequency::Daily));
+
+    let unrecognised: Vec<&str> = discovery.unrecognised.iter()
+        .map(|p| p.file_name().unwrap().to_str().unwrap())
+        .collect();
+    assert_eq!(unrecognised, [
+        "bundesbank_bulk.csv", "bundesbank_de.csv", "ecb_portal.csv", "ecb_sdmx.csv",
+        "emmi_daily_2024.csv", "emmi_hist_2024.csv",
+    ]);
+}
+
+#[test]
+fn discovery_sorts_series_by_frequency_and_tenor() {
+    let dir = std::env::temp_dir().join(format!("euribor-discovery-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let csv = fixture("BBIG1.D.D0.EUR.MMKT.EURIBOR.M03.BID._Z.csv");
+    fs::copy(&csv, dir.join("BBIG1.M.D0.EUR.MMKT.EURIBOR.M01.BID._Z.csv")).unwrap();
+    fs::copy(&csv, dir.join("BBIG1.D.D0.EUR.MMKT.EURIBOR.W01.BID._Z.csv")).unwrap();
+    fs::copy(fixture("BBIG1.D.D0.EUR.MMKT.EURIBOR.M03.BID._Z.csv.gz"), dir.join("BBIG1.D.D0.EUR.MMKT.EURIBOR.M12.BID._Z.csv.gz")).unwrap();
+    fs::copy(fixture("bundesbank_m03.zip"), dir.join("download.zip")).unwrap();
+    fs::write(dir.join("notes.txt"), "").unwrap();
+
+    let discovery = discover(&dir);
+    fs::remove_dir_all(&dir).unwrap();
+    let discovery = discovery.unwrap();
+
+    let keys: Vec<(Frequency, Tenor, bool)> = discovery.files.iter()
+        .map(|f| (f.key.frequency, f.key.tenor, f.entry.is_some()))
+        .collect();
+    assert_eq!(keys, [
+        (Frequency::Daily, Tenor::weeks(1), false),
+        (Frequency::Daily, Tenor::months(3), true),
+        (Frequency::Daily, Tenor::months(12), false),
+        (Frequency::Monthly, Tenor::months(1), false),
+    ]);
+    assert_eq!(discovery.frequencies(), [Frequency::Daily, Frequency::Monthly]);
+    assert_eq!(discovery.unrecognised, [dir.join("notes.txt")]);
+}
+
+#[test]
+fn zero_length_tenors_are_not_recognised() {
+    assert_eq!(tenor_from_label("0m"), None);
+    assert_eq!(tenor_from_label("0 weeks"), None);
+    assert_eq!(tenor_from_ecb_key("FM.D.U2.EUR.RT.MM.EURIBOR0MD_.HSTA"), None);
+    assert_eq!(tenor_from_ecb_key("FM.D.U2.EUR.RT.MM.EURIBOR0WD_.HSTA"), None);
+
+    let all_rates = parse_emmi_csv(b"Date,0m,3m\n02/01/2024,3.1,3.9\n", &LoadOptions::default()).unwrap();
+    assert_eq!(all_rates.tenors().collect::<Vec<_>>(), [Tenor::months(3)]);
+    assert!(calculate_average_rates(&all_rates, &[30], &AverageOptions::default()).is_ok());
+}